@@ -0,0 +1,99 @@
+#!/usr/bin/env python3
+"""Synthesizes the book's sound effects, ambience and music as WAV files.
+
+Everything here is generated from scratch, so the sounds are free to use (CC0). Run it from
+this directory to rebuild them:
+
+    python3 generate.py
+"""
+
+import math
+import random
+import struct
+import wave
+
+RATE = 11025
+
+
+def write(name, samples):
+    with wave.open(name, "wb") as out:
+        out.setnchannels(1)
+        out.setsampwidth(2)
+        out.setframerate(RATE)
+        clipped = (max(-1.0, min(1.0, s)) for s in samples)
+        out.writeframes(b"".join(struct.pack("<h", int(s * 32767)) for s in clipped))
+
+
+def lowpass(samples, amount):
+    out, last = [], 0.0
+    for s in samples:
+        last += (s - last) * amount
+        out.append(last)
+    return out
+
+
+def knock(seconds, pitch, decay, rng):
+    """A short wooden thud: a falling sine with a click of noise on top."""
+    samples = []
+    for i in range(int(RATE * seconds)):
+        t = i / RATE
+        tone = math.sin(2 * math.pi * pitch * t * (1 - t)) * math.exp(-t * decay)
+        click = rng.uniform(-1, 1) * math.exp(-t * decay * 6)
+        samples.append(0.6 * tone + 0.3 * click)
+    return samples
+
+
+def gavel(rng):
+    return knock(0.5, 180, 14, rng)
+
+
+def footsteps(rng):
+    samples = [0.0] * int(RATE * 1.9)
+    for step in range(4):
+        thud = lowpass(knock(0.25, 90 + 15 * (step % 2), 30, rng), 0.3)
+        start = int(RATE * 0.45 * step)
+        for i, s in enumerate(thud):
+            samples[start + i] += 0.7 * s
+    return samples
+
+
+def murmur(rng):
+    """Crowd murmur, looping every 4 seconds."""
+    seconds = 4
+    noise = lowpass([rng.uniform(-1, 1) for _ in range(RATE * seconds)], 0.08)
+    samples = []
+    for i, s in enumerate(noise):
+        t = i / RATE
+        # swells that line up with the loop point
+        swell = 0.6 + 0.25 * math.sin(2 * math.pi * t / seconds) + 0.15 * math.sin(
+            2 * math.pi * 3 * t / seconds
+        )
+        samples.append(1.6 * s * swell)
+    return samples
+
+
+def theme(rng):
+    """A slow minor arpeggio over Am, F, Dm and E, looping every 8 seconds."""
+    chords = [(57, 60, 64), (53, 57, 60), (50, 53, 57), (52, 56, 59)]
+    beat = 0.25
+    samples = [0.0] * int(RATE * beat * 8 * len(chords))
+    for c, chord in enumerate(chords):
+        notes = [chord[0] - 12] + [chord[i % 3] + 12 * (i // 3) for i in range(7)]
+        for n, note in enumerate(notes):
+            freq = 440 * 2 ** ((note - 69) / 12)
+            start = int(RATE * beat * (8 * c + n))
+            length = RATE * beat * (8 if n == 0 else 3)
+            for i in range(int(length)):
+                t = i / RATE
+                index = (start + i) % len(samples)
+                env = math.exp(-t * (1.2 if n == 0 else 4))
+                samples[index] += 0.22 * env * math.sin(2 * math.pi * freq * t)
+    return samples
+
+
+if __name__ == "__main__":
+    rng = random.Random(4843)
+    write("gavel.wav", gavel(rng))
+    write("footsteps.wav", footsteps(rng))
+    write("courtroom_murmur.wav", murmur(rng))
+    write("courtroom_theme.wav", theme(rng))
@@ -0,0 +1,259 @@
+use crate::enums::SoundEffect;
+use raylib::core::audio::{Music, RaylibAudio, Sound, Wave};
+use raylib::core::logging::trace_log;
+use raylib::ffi::TraceLogType;
+use raylib::RaylibThread;
+use std::collections::HashMap;
+
+/// Volume groups. [`Channel::Master`] scales all of the others.
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum Channel {
+    Master,
+    Music,
+    Ambience,
+    Effects,
+}
+
+/// A streamed, looping track playing on the music or ambience channel.
+struct Track {
+    channel: Channel,
+    path: &'static str,
+    stream: Music,
+    /// How far faded in the track is, from 0 to 1.
+    gain: f32,
+    /// Where `gain` is heading; tracks that fade out to 0 are dropped.
+    target: f32,
+    /// Change of `gain` per frame.
+    fade_step: f32,
+}
+
+/// Plays music, ambience and sound effects.
+///
+/// When there's no audio device every call is silently ignored, so callers never have to check.
+pub struct AudioManager {
+    // streams and sounds have to be unloaded before the device is closed, which is the order
+    // fields are dropped in
+    tracks: Vec<Track>,
+    sounds: HashMap<SoundEffect, Sound>,
+    /// The typewriter blip, synthesized on startup.
+    blip: Option<Sound>,
+    volumes: HashMap<Channel, f32>,
+    device: Option<RaylibAudio>,
+}
+
+impl AudioManager {
+    /// Opens the audio device and loads every sound effect. Falls back to [`AudioManager::silent`]
+    /// if there is no device.
+    pub fn new() -> AudioManager {
+        let device = RaylibAudio::init_audio_device();
+        if !device.is_audio_device_ready() {
+            trace_log(
+                TraceLogType::LOG_WARNING,
+                "no audio device: continuing without sound",
+            );
+            return AudioManager::silent();
+        }
+
+        let mut sounds = HashMap::new();
+        for effect in SoundEffect::ALL.iter() {
+            match Sound::load_sound(effect.path()) {
+                Ok(sound) => {
+                    sounds.insert(*effect, sound);
+                }
+                Err(e) => trace_log(TraceLogType::LOG_WARNING, &e),
+            }
+        }
+
+        let mut audio = AudioManager {
+            sounds,
+            blip: load_blip(),
+            device: Some(device),
+            ..AudioManager::silent()
+        };
+        for channel in [Channel::Master, Channel::Effects].iter() {
+            audio.set_volume(*channel, audio.volume(*channel));
+        }
+        audio
+    }
+
+    /// An audio manager that plays nothing.
+    pub fn silent() -> AudioManager {
+        let volumes = [
+            (Channel::Master, 1.0),
+            (Channel::Music, 0.7),
+            (Channel::Ambience, 0.5),
+            (Channel::Effects, 1.0),
+        ];
+        AudioManager {
+            tracks: Vec::new(),
+            sounds: HashMap::new(),
+            blip: None,
+            volumes: volumes.iter().copied().collect(),
+            device: None,
+        }
+    }
+
+    pub fn volume(&self, channel: Channel) -> f32 {
+        self.volumes.get(&channel).copied().unwrap_or(1.0)
+    }
+
+    pub fn set_volume(&mut self, channel: Channel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        self.volumes.insert(channel, volume);
+        if let Some(device) = self.device.as_mut() {
+            match channel {
+                Channel::Master => device.set_master_volume(volume),
+                Channel::Effects => {
+                    for sound in self.sounds.values().chain(self.blip.iter()) {
+                        device.set_sound_volume(sound, volume);
+                    }
+                }
+                // tracks pick up their volume on the next update
+                Channel::Music | Channel::Ambience => {}
+            }
+        }
+    }
+
+    /// Starts looping a track on the music or ambience channel, crossfading over `fade` frames
+    /// from whatever that channel was playing. Playing the track that is already on is a no-op.
+    pub fn play_track(
+        &mut self,
+        thread: &RaylibThread,
+        channel: Channel,
+        path: &'static str,
+        fade: i32,
+    ) {
+        let device = match self.device.as_mut() {
+            Some(device) => device,
+            None => return,
+        };
+        if self
+            .tracks
+            .iter()
+            .any(|t| t.channel == channel && t.path == path && t.target > 0.0)
+        {
+            return;
+        }
+        let mut stream = match Music::load_music_stream(thread, path) {
+            Ok(stream) => stream,
+            Err(e) => {
+                trace_log(TraceLogType::LOG_WARNING, &e);
+                return;
+            }
+        };
+
+        let fade_step = 1.0 / fade.max(1) as f32;
+        for track in self.tracks.iter_mut().filter(|t| t.channel == channel) {
+            track.target = 0.0;
+            track.fade_step = fade_step;
+        }
+        device.set_music_volume(&mut stream, 0.0);
+        device.play_music_stream(&mut stream);
+        self.tracks.push(Track {
+            channel,
+            path,
+            stream,
+            gain: 0.0,
+            target: 1.0,
+            fade_step,
+        });
+    }
+
+    /// Fades out whatever the channel is playing over `fade` frames.
+    pub fn stop_track(&mut self, channel: Channel, fade: i32) {
+        let fade_step = 1.0 / fade.max(1) as f32;
+        for track in self.tracks.iter_mut().filter(|t| t.channel == channel) {
+            track.target = 0.0;
+            track.fade_step = fade_step;
+        }
+    }
+
+    pub fn play_sound(&mut self, effect: SoundEffect) {
+        if let (Some(device), Some(sound)) = (self.device.as_mut(), self.sounds.get(&effect)) {
+            device.play_sound_multi(sound);
+        }
+    }
+
+    /// Plays the typewriter blip. A `pitch` of 1 is the narrator's voice.
+    pub fn play_blip(&mut self, pitch: f32) {
+        if let (Some(device), Some(blip)) = (self.device.as_mut(), self.blip.as_ref()) {
+            device.set_sound_pitch(blip, pitch);
+            device.play_sound(blip);
+        }
+    }
+
+    /// Keeps streams fed and fades moving. Call once per frame.
+    pub fn update(&mut self) {
+        let device = match self.device.as_mut() {
+            Some(device) => device,
+            None => return,
+        };
+        for track in self.tracks.iter_mut() {
+            if track.gain < track.target {
+                track.gain = (track.gain + track.fade_step).min(track.target);
+            } else {
+                track.gain = (track.gain - track.fade_step).max(track.target);
+            }
+            let channel = self.volumes.get(&track.channel).copied().unwrap_or(1.0);
+            device.set_music_volume(&mut track.stream, track.gain * channel);
+            device.update_music_stream(&mut track.stream);
+        }
+
+        self.tracks.retain(|t| t.gain > 0.0 || t.target > 0.0);
+    }
+}
+
+fn load_blip() -> Option<Sound> {
+    let wav = blip_wav(22050, 0.04, 660.0);
+    let file_type = std::ffi::CString::new(".wav").unwrap();
+    // `Wave` takes over freeing the samples
+    let wave = unsafe {
+        Wave::from_raw(raylib::ffi::LoadWaveFromMemory(
+            file_type.as_ptr(),
+            wav.as_ptr(),
+            wav.len() as i32,
+        ))
+    };
+    Sound::load_sound_from_wave(&wave)
+        .map_err(|e| trace_log(TraceLogType::LOG_WARNING, &e))
+        .ok()
+}
+
+/// Synthesizes a short square wave beep that dies off quickly, as a 16 bit mono WAV file.
+fn blip_wav(sample_rate: u32, seconds: f32, frequency: f32) -> Vec<u8> {
+    let samples = (sample_rate as f32 * seconds) as u32;
+    let data_len = samples * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // bytes per second
+    wav.extend_from_slice(&2u16.to_le_bytes()); // bytes per frame
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+
+    for i in 0..samples {
+        let t = i as f32 / sample_rate as f32;
+        let square = if (t * frequency).fract() < 0.5 {
+            1.0
+        } else {
+            -1.0
+        };
+        let envelope = (-t / seconds * 5.0).exp();
+        let sample = (square * envelope * 0.3 * i16::MAX as f32) as i16;
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+impl Default for AudioManager {
+    fn default() -> AudioManager {
+        AudioManager::silent()
+    }
+}
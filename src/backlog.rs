@@ -0,0 +1,128 @@
+use crate::cast::split_speaker;
+use crate::drawing::{draw_text, wrap_text};
+use crate::glossary::strip_markup;
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::math::Rectangle;
+use raylib::ffi::KeyboardKey;
+
+const FONT_SIZE: i32 = 20;
+const LINE_HEIGHT: i32 = 24;
+/// Space between two entries.
+const GAP: i32 = 14;
+/// Where entries are drawn on screen.
+const VIEW: Rectangle = Rectangle {
+    x: 82.0,
+    y: 90.0,
+    width: 460.0,
+    height: 450.0,
+};
+
+/// A log of every text box line shown this session, which the reader can scroll through.
+pub struct Backlog {
+    /// Script lines, speaker tags included, oldest first.
+    lines: Vec<&'static str>,
+    open: bool,
+    /// Pixels scrolled up from the newest line.
+    scroll: i32,
+}
+
+impl Backlog {
+    pub fn new() -> Backlog {
+        Backlog {
+            lines: Vec::new(),
+            open: false,
+            scroll: 0,
+        }
+    }
+
+    /// Adds a line that was just shown. Showing the same line twice in a row logs it once.
+    pub fn push(&mut self, line: &'static str) {
+        if self.lines.last() != Some(&line) {
+            self.lines.push(line);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens the log scrolled to the newest line.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.scroll = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Handles scrolling and draws the log. Call at the end of every frame, after the scene.
+    pub fn draw(&mut self, d: &mut RaylibDrawHandle) {
+        if !self.open {
+            return;
+        }
+
+        // lay out every entry from the bottom up, in pixels above the bottom of the view
+        let mut entries = Vec::with_capacity(self.lines.len());
+        let mut height = 0;
+        for line in self.lines.iter().rev() {
+            let (speaker, text) = split_speaker(line);
+            let text = strip_markup(text);
+            let mut rows: Vec<(String, Color)> = wrap_text(&text, FONT_SIZE, VIEW.width as i32)
+                .into_iter()
+                .map(|row| (row, Color::BLACK))
+                .collect();
+            if let Some(speaker) = speaker {
+                rows.insert(0, (speaker.display_name(), Color::DARKGRAY));
+            }
+            height += rows.len() as i32 * LINE_HEIGHT;
+            entries.push((height, rows));
+            height += GAP;
+        }
+
+        let max_scroll = (height - GAP - VIEW.height as i32).max(0);
+        let wheel = d.get_mouse_wheel_move();
+        if d.is_key_down(KeyboardKey::KEY_UP) || wheel > 0.0 {
+            self.scroll += if wheel > 0.0 { LINE_HEIGHT * 2 } else { 6 };
+        } else if d.is_key_down(KeyboardKey::KEY_DOWN) || wheel < 0.0 {
+            // scrolling down past the newest line goes back to reading
+            if self.scroll == 0 && wheel < 0.0 {
+                self.close();
+            }
+            self.scroll -= if wheel < 0.0 { LINE_HEIGHT * 2 } else { 6 };
+        }
+        self.scroll = self.scroll.clamp(0, max_scroll);
+
+        d.draw_rectangle(0, 0, 624, 624, Color::BLACK.fade(0.6));
+        d.draw_rectangle_rounded(
+            Rectangle::new(62.0, 30.0, 500.0, 564.0),
+            0.05,
+            10,
+            Color::WHITE,
+        );
+        draw_text(d, "Backlog", 30, 312, 60, None);
+
+        let bottom = (VIEW.y + VIEW.height) as i32 + self.scroll;
+        for (top, rows) in entries.iter() {
+            for (i, (row, color)) in rows.iter().enumerate() {
+                let y = bottom - top + i as i32 * LINE_HEIGHT;
+                // rows partly outside of the view are left out rather than clipped
+                if y >= VIEW.y as i32 && y + LINE_HEIGHT <= bottom - self.scroll {
+                    d.draw_text(row, VIEW.x as i32, y, FONT_SIZE, *color);
+                }
+            }
+        }
+        if self.lines.is_empty() {
+            draw_text(d, "Nothing to show yet.", FONT_SIZE, 312, 300, None);
+        }
+
+        draw_text(d, "UP/DOWN to scroll, L to close", 20, 312, 570, None);
+    }
+}
+
+impl Default for Backlog {
+    fn default() -> Backlog {
+        Backlog::new()
+    }
+}
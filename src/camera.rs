@@ -0,0 +1,124 @@
+use crate::cast::Actor;
+use crate::tween::{ease, Tween};
+use raylib::core::camera::Camera2D;
+use raylib::core::math::{Rectangle, Vector2};
+
+/// Width and height of the courtroom in world coordinates, which is also the window size.
+pub const ROOM_SIZE: f32 = 624.0;
+
+/// A camera move a script can ask for. While no shot is active the camera shows the whole room.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Shot {
+    /// Keeps an actor centered at the given zoom, following them if they move.
+    Focus(Actor, f32),
+    /// Looks at a fixed point of the room at the given zoom.
+    Look(f32, f32, f32),
+}
+
+/// A 2D camera that eases towards wherever it was last told to look, never showing anything
+/// outside of the room.
+pub struct Camera {
+    center: Vector2,
+    zoom: f32,
+    target_center: Vector2,
+    target_zoom: f32,
+    center_tween: Tween<Vector2>,
+    zoom_tween: Tween<f32>,
+    /// Offset and zoom factor from screen effects, applied on top of the eased position.
+    shake: Vector2,
+    punch: f32,
+}
+
+impl Camera {
+    /// Frames it takes to ease to a new target. A target that keeps moving is chased instead.
+    const MOVE_FRAMES: f32 = 45.0;
+
+    pub fn new() -> Camera {
+        let center = Vector2::new(ROOM_SIZE / 2.0, ROOM_SIZE / 2.0);
+        Camera {
+            center,
+            zoom: 1.0,
+            target_center: center,
+            target_zoom: 1.0,
+            center_tween: Tween::new(center, center, 0.0, ease::cubic_out),
+            zoom_tween: Tween::new(1.0, 1.0, 0.0, ease::cubic_out),
+            shake: Vector2::new(0.0, 0.0),
+            punch: 1.0,
+        }
+    }
+
+    pub fn look_at(&mut self, x: f32, y: f32, zoom: f32) {
+        let (center, zoom) = (Vector2::new(x, y), zoom.max(1.0));
+        if center != self.target_center {
+            self.target_center = center;
+            self.center_tween =
+                Tween::new(self.center, center, Camera::MOVE_FRAMES, ease::cubic_out);
+        }
+        if zoom != self.target_zoom {
+            self.target_zoom = zoom;
+            self.zoom_tween = Tween::new(self.zoom, zoom, Camera::MOVE_FRAMES, ease::cubic_out);
+        }
+    }
+
+    /// Goes back to showing the whole room.
+    pub fn reset(&mut self) {
+        self.look_at(ROOM_SIZE / 2.0, ROOM_SIZE / 2.0, 1.0);
+    }
+
+    /// Jumps straight to the target without easing.
+    pub fn snap(&mut self) {
+        let (center, zoom) = (self.target_center, self.target_zoom);
+        self.center_tween = Tween::new(center, center, 0.0, ease::cubic_out);
+        self.zoom_tween = Tween::new(zoom, zoom, 0.0, ease::cubic_out);
+        self.update();
+    }
+
+    /// Moves one frame closer to the target. Call once per frame.
+    pub fn update(&mut self) {
+        self.zoom = self.zoom_tween.advance(1.0);
+        self.center = self.center_tween.advance(1.0);
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        let half_view = ROOM_SIZE / self.zoom / 2.0;
+        self.center.x = self.center.x.max(half_view).min(ROOM_SIZE - half_view);
+        self.center.y = self.center.y.max(half_view).min(ROOM_SIZE - half_view);
+    }
+
+    /// Sets this frame's shake offset and zoom punch. Unlike shots these aren't eased or
+    /// clamped to the room.
+    pub fn jolt(&mut self, shake: Vector2, punch: f32) {
+        self.shake = shake;
+        self.punch = punch;
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom * self.punch
+    }
+
+    pub fn camera2d(&self) -> Camera2D {
+        Camera2D {
+            offset: Vector2::new(ROOM_SIZE / 2.0, ROOM_SIZE / 2.0) + self.shake,
+            target: self.center,
+            rotation: 0.0,
+            zoom: self.zoom(),
+        }
+    }
+
+    pub fn world_to_screen(&self, rec: Rectangle) -> Rectangle {
+        let zoom = self.zoom();
+        Rectangle::new(
+            (rec.x - self.center.x) * zoom + ROOM_SIZE / 2.0 + self.shake.x,
+            (rec.y - self.center.y) * zoom + ROOM_SIZE / 2.0 + self.shake.y,
+            rec.width * zoom,
+            rec.height * zoom,
+        )
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Camera {
+        Camera::new()
+    }
+}
@@ -0,0 +1,144 @@
+use crate::enums::{GnomeType, PersonType};
+use crate::jury::{JurorId, Jury};
+
+/// The named characters of the story.
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum CastMember {
+    Judge,
+    Steve,
+    King,
+    DefenseLawyer,
+    Petrocelli,
+}
+
+/// Everything the engine needs to know to put a cast member on stage.
+pub struct CastEntry {
+    pub person_type: PersonType,
+    /// Default position in screen coordinates.
+    pub x: f32,
+    pub y: f32,
+    /// The name shown to the reader.
+    pub display_name: &'static str,
+}
+
+impl CastMember {
+    pub const ALL: [CastMember; 5] = [
+        CastMember::Judge,
+        CastMember::Steve,
+        CastMember::King,
+        CastMember::DefenseLawyer,
+        CastMember::Petrocelli,
+    ];
+
+    pub fn entry(self) -> CastEntry {
+        match self {
+            CastMember::Judge => CastEntry {
+                person_type: PersonType::Gnome(GnomeType::WalkingForward1),
+                x: 291.0,
+                y: 48.0,
+                display_name: "Judge",
+            },
+            CastMember::Steve => CastEntry {
+                person_type: PersonType::Person4,
+                x: 496.0,
+                y: 210.0,
+                display_name: "Steve",
+            },
+            CastMember::King => CastEntry {
+                person_type: PersonType::Person5,
+                x: 400.0,
+                y: 210.0,
+                display_name: "King",
+            },
+            CastMember::DefenseLawyer => CastEntry {
+                person_type: PersonType::Person1,
+                x: 448.0,
+                y: 210.0,
+                display_name: "O'Brien",
+            },
+            CastMember::Petrocelli => CastEntry {
+                person_type: PersonType::Person12,
+                x: 128.0,
+                y: 210.0,
+                display_name: "Petrocelli",
+            },
+        }
+    }
+
+    /// Looks up a cast member by the name used in scripts. Case insensitive.
+    pub fn from_name(name: &str) -> Option<CastMember> {
+        let name = name.trim();
+        CastMember::ALL
+            .iter()
+            .copied()
+            .find(|c| c.entry().display_name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Anyone a script can point at: a named cast member or a single juror.
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum Actor {
+    Cast(CastMember),
+    Juror(JurorId),
+}
+
+impl Actor {
+    /// Parses a script name: either a cast member (`Steve`) or a juror seat (`Juror 7`).
+    pub fn from_name(name: &str) -> Option<Actor> {
+        let name = name.trim();
+        if let Some(member) = CastMember::from_name(name) {
+            return Some(Actor::Cast(member));
+        }
+        let (word, seat) = name.split_once(' ')?;
+        if !word.eq_ignore_ascii_case("juror") {
+            return None;
+        }
+        match seat.trim().parse() {
+            Ok(seat @ 1..=12) => Some(Actor::Juror(JurorId(seat))),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(self) -> String {
+        match self {
+            Actor::Cast(member) => member.entry().display_name.to_string(),
+            Actor::Juror(JurorId(seat)) => format!("Juror {}", seat),
+        }
+    }
+
+    /// Pitch of the actor's typewriter blips, where 1 is the narrator.
+    pub fn voice_pitch(self) -> f32 {
+        match self {
+            Actor::Cast(CastMember::Judge) => 0.7,
+            Actor::Cast(CastMember::Steve) => 1.1,
+            Actor::Cast(CastMember::King) => 0.85,
+            Actor::Cast(CastMember::DefenseLawyer) => 1.25,
+            Actor::Cast(CastMember::Petrocelli) => 1.35,
+            // spread the jurors around the narrator's pitch
+            Actor::Juror(JurorId(seat)) => 0.8 + seat as f32 * 0.03,
+        }
+    }
+
+    /// Finds what the actor looks like and where they currently are on screen.
+    pub fn locate(self, jury: &Jury) -> Option<(PersonType, f32, f32)> {
+        match self {
+            Actor::Cast(member) => {
+                let entry = member.entry();
+                Some((entry.person_type, entry.x, entry.y))
+            }
+            Actor::Juror(id) => jury.get(id).map(|j| (j.person_type, j.x, j.y)),
+        }
+    }
+}
+
+/// Splits a script line of the form `Steve: ...` into its speaker and text.
+///
+/// Lines without a known speaker tag are returned whole as narration.
+pub fn split_speaker(line: &str) -> (Option<Actor>, &str) {
+    if let Some((tag, text)) = line.split_once(':') {
+        if let Some(speaker) = Actor::from_name(tag) {
+            return (Some(speaker), text.trim_start());
+        }
+    }
+    (None, line)
+}
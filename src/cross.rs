@@ -0,0 +1,322 @@
+use crate::cast::{split_speaker, Actor};
+use crate::drawing::{draw_text, draw_text_box};
+use crate::enums::PersonType;
+use crate::evidence::{collected, draw_award_notice, draw_evidence_list, Evidence};
+use crate::glossary::Glossary;
+use crate::jury::sway;
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::math::Rectangle;
+use raylib::core::texture::Texture2D;
+use raylib::ffi::KeyboardKey;
+use std::collections::{BTreeMap, HashMap};
+
+/// One line of a witness's testimony.
+pub struct Statement {
+    pub text: &'static str,
+    /// What follows pressing the witness on this line.
+    pub press: &'static [&'static str],
+    /// The evidence that contradicts this line, and what follows presenting it.
+    pub contradiction: Option<(Evidence, &'static [&'static str])>,
+}
+
+/// A witness on the stand, cross-examined until the reader finds the contradiction.
+///
+/// Lines may start with a speaker tag like script lines do, and the witness's own name also
+/// works as a tag.
+pub struct Testimony {
+    pub witness: &'static str,
+    pub witness_type: PersonType,
+    /// Where the witness sits, in world coordinates.
+    pub x: f32,
+    pub y: f32,
+    /// Lines before the testimony starts, each of which may hand the reader evidence.
+    pub intro: &'static [(&'static str, Option<Evidence>)],
+    pub statements: &'static [Statement],
+    /// Follows presenting evidence that doesn't contradict the line.
+    pub wrong: &'static [&'static str],
+    /// Awarded once the contradiction is found.
+    pub award: Evidence,
+}
+
+#[derive(Copy, Clone)]
+enum Phase {
+    Intro(usize),
+    Testimony,
+    /// Lines playing out after pressing or presenting, and whether they end the testimony.
+    Responding(&'static [&'static str], usize, bool),
+    /// Picking evidence to present.
+    Presenting(usize),
+    Done,
+}
+
+/// Where the reader is in a cross-examination.
+pub struct CrossExamination {
+    testimony: &'static Testimony,
+    phase: Phase,
+    statement: usize,
+}
+
+impl CrossExamination {
+    pub fn new(testimony: &'static Testimony) -> CrossExamination {
+        CrossExamination {
+            testimony,
+            phase: Phase::Intro(0),
+            statement: 0,
+        }
+    }
+
+    /// The line on screen, if any.
+    fn line(&self) -> Option<&'static str> {
+        match self.phase {
+            Phase::Intro(i) => self.testimony.intro.get(i).map(|(line, _)| *line),
+            Phase::Testimony | Phase::Presenting(_) => {
+                let statement = self.testimony.statements.get(self.statement)?;
+                Some(statement.text)
+            }
+            Phase::Responding(lines, i, _) => lines.get(i).copied(),
+            Phase::Done => None,
+        }
+    }
+
+    /// Splits a line into its speaker's name, look and position, and the text. Statements
+    /// are always the witness's.
+    fn speaker(
+        &self,
+        line: &'static str,
+    ) -> (Option<(String, PersonType, f32, f32)>, &'static str) {
+        let testimony = self.testimony;
+        let witness = (
+            testimony.witness.to_string(),
+            testimony.witness_type,
+            testimony.x,
+            testimony.y,
+        );
+        if let Phase::Testimony | Phase::Presenting(_) = self.phase {
+            return (Some(witness), line);
+        }
+        if let Some(text) = line
+            .strip_prefix(testimony.witness)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            return (Some(witness), text.trim_start());
+        }
+
+        match split_speaker(line) {
+            (Some(Actor::Cast(member)), text) => {
+                let entry = member.entry();
+                let name = entry.display_name.to_string();
+                (Some((name, entry.person_type, entry.x, entry.y)), text)
+            }
+            (_, text) => (None, text),
+        }
+    }
+
+    /// Who is talking right now, as their look and position, for the camera to follow.
+    pub fn current_speaker(&self) -> Option<(PersonType, f32, f32)> {
+        let (speaker, _) = self.speaker(self.line()?);
+        speaker.map(|(_, person_type, x, y)| (person_type, x, y))
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self.phase, Phase::Done)
+    }
+
+    /// Handles input and draws the text box and prompts. Call after drawing the courtroom.
+    pub fn draw(
+        &mut self,
+        d: &mut RaylibDrawHandle,
+        person_texture: &HashMap<PersonType, Texture2D>,
+        glossary: &Glossary,
+        variables: &mut BTreeMap<String, i32>,
+        paused: bool,
+    ) {
+        let testimony = self.testimony;
+        if !paused {
+            self.handle_input(d, variables);
+        }
+        let awarded = match self.phase {
+            Phase::Intro(i) => testimony.intro.get(i).and_then(|(_, evidence)| *evidence),
+            _ => None,
+        };
+        if let Some(evidence) = awarded {
+            variables.insert(evidence.variable().to_string(), 1);
+        }
+
+        if let Some(line) = self.line() {
+            let (speaker, text) = self.speaker(line);
+            draw_text_box(
+                d,
+                person_texture,
+                glossary,
+                text,
+                speaker.as_ref().map(|(name, p, _, _)| (name.as_str(), *p)),
+            );
+        }
+
+        let hint = match self.phase {
+            Phase::Intro(_) | Phase::Responding(..) => "ENTER to continue".to_string(),
+            Phase::Testimony => format!(
+                "Statement {}/{}   LEFT/RIGHT: browse   P: press   E: present evidence",
+                self.statement + 1,
+                testimony.statements.len()
+            ),
+            Phase::Presenting(_) => "ENTER to present, BACKSPACE to go back".to_string(),
+            Phase::Done => String::new(),
+        };
+        if let Some(evidence) = awarded {
+            draw_award_notice(d, evidence);
+        } else if !hint.is_empty() {
+            d.draw_rectangle(0, 0, 624, 30, Color::WHITE.fade(0.85));
+            draw_text(d, &hint, 16, 312, 15, None);
+        }
+
+        if let Phase::Presenting(selected) = self.phase {
+            d.draw_rectangle(0, 30, 624, 594, Color::BLACK.fade(0.6));
+            d.draw_rectangle_rounded(
+                Rectangle::new(24.0, 50.0, 576.0, 420.0),
+                0.05,
+                10,
+                Color::WHITE,
+            );
+            draw_text(d, "Present which evidence?", 24, 312, 76, None);
+            draw_evidence_list(d, &collected(variables), selected, 110);
+        }
+    }
+
+    fn handle_input(&mut self, d: &RaylibDrawHandle, variables: &mut BTreeMap<String, i32>) {
+        let testimony = self.testimony;
+        let enter = d.is_key_pressed(KeyboardKey::KEY_ENTER);
+        self.phase = match self.phase {
+            Phase::Intro(i) if enter => {
+                if i + 1 < testimony.intro.len() {
+                    Phase::Intro(i + 1)
+                } else {
+                    Phase::Testimony
+                }
+            }
+            Phase::Responding(lines, i, solved) if enter => {
+                if i + 1 < lines.len() {
+                    Phase::Responding(lines, i + 1, solved)
+                } else if solved {
+                    variables.insert(testimony.award.variable().to_string(), 1);
+                    Phase::Done
+                } else {
+                    Phase::Testimony
+                }
+            }
+            Phase::Testimony => {
+                let count = testimony.statements.len();
+                let statement = &testimony.statements[self.statement];
+                if d.is_key_pressed(KeyboardKey::KEY_RIGHT) {
+                    self.statement = (self.statement + 1) % count;
+                } else if d.is_key_pressed(KeyboardKey::KEY_LEFT) {
+                    self.statement = (self.statement + count - 1) % count;
+                }
+                if d.is_key_pressed(KeyboardKey::KEY_P) && !statement.press.is_empty() {
+                    Phase::Responding(statement.press, 0, false)
+                } else if d.is_key_pressed(KeyboardKey::KEY_E) {
+                    Phase::Presenting(0)
+                } else {
+                    Phase::Testimony
+                }
+            }
+            Phase::Presenting(selected) => {
+                let evidence = collected(variables);
+                let statement = &testimony.statements[self.statement];
+                if d.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+                    Phase::Testimony
+                } else if evidence.is_empty() {
+                    Phase::Presenting(0)
+                } else if d.is_key_pressed(KeyboardKey::KEY_UP) {
+                    Phase::Presenting((selected + evidence.len() - 1) % evidence.len())
+                } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+                    Phase::Presenting((selected + 1) % evidence.len())
+                } else if enter {
+                    match statement.contradiction {
+                        Some((contradicts, lines)) if contradicts == evidence[selected] => {
+                            sway(variables, None, 2);
+                            Phase::Responding(lines, 0, true)
+                        }
+                        _ => {
+                            sway(variables, None, -1);
+                            Phase::Responding(testimony.wrong, 0, false)
+                        }
+                    }
+                } else {
+                    Phase::Presenting(selected)
+                }
+            }
+            phase => phase,
+        };
+    }
+}
+
+/// Bobo Evans, cross-examined by O'Brien.
+pub const BOBO_EVANS: Testimony = Testimony {
+    witness: "Bobo",
+    witness_type: PersonType::Person3,
+    x: 435.0,
+    y: 48.0,
+    intro: &[
+        (
+            "Earlier in the {trial}, Lorelle Henry {testified} that she was in the drugstore the \
+            day Mr. Nesbitt was killed.",
+            Some(Evidence::HenryTestimony),
+        ),
+        (
+            "Petrocelli showed the jury photos of the drugstore after the robbery.",
+            Some(Evidence::DrugstorePhoto),
+        ),
+        (
+            "Petrocelli: {The People} call Richard \"Bobo\" Evans.",
+            None,
+        ),
+        ("Judge: Ms. O'Brien, your {witness}.", None),
+    ],
+    statements: &[
+        Statement {
+            text: "Me and King went into the drugstore to get some cigarettes and some money.",
+            press: &[
+                "O'Brien: Whose idea was it to rob the drugstore?",
+                "Bobo: King's. And that kid over there was in on it too.",
+            ],
+            contradiction: None,
+        },
+        Statement {
+            text: "Steve went in first to check the place out. If it was cool, he'd come out \
+            and give us the sign.",
+            press: &[
+                "O'Brien: What sign was he supposed to give?",
+                "Bobo: He was just supposed to come out, you know. Let us know there were no \
+                cops around.",
+            ],
+            contradiction: Some((
+                Evidence::HenryTestimony,
+                &[
+                    "O'Brien: Mrs. Henry was in that drugstore. She picked King out of a \
+                    lineup, but she never saw Steve there.",
+                    "Bobo: I didn't say I saw him give no signal. I just know he was supposed \
+                    to.",
+                    "O'Brien: So you never saw Steve Harmon give any signal at all?",
+                    "Bobo: ...No.",
+                    "O'Brien: Nothing further, Your Honor.",
+                ],
+            )),
+        },
+        Statement {
+            text: "I ain't getting nothing for {testifying}. I'm just telling what happened.",
+            press: &[
+                "O'Brien: Nothing at all? Weren't your charges reduced in exchange for your \
+                {testimony} today?",
+                "Bobo: ...Yeah. I got a {deal}.",
+            ],
+            contradiction: None,
+        },
+    ],
+    wrong: &[
+        "Petrocelli: {Objection}! Counsel is wasting the court's time.",
+        "Judge: {Sustained}. Move along, Ms. O'Brien.",
+    ],
+    award: Evidence::BoboTestimony,
+};
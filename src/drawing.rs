@@ -1,15 +1,16 @@
 use crate::enums::*;
+use crate::glossary::{draw_marked_text, Glossary};
 use raylib::core::color::Color;
 use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
 use raylib::core::math::{Rectangle, Vector2};
 use raylib::core::text::measure_text;
 use raylib::core::texture::Texture2D;
-use raylib::ffi::{MouseButton, MouseCursor};
+use raylib::ffi::{KeyboardKey, MouseButton, MouseCursor};
 use raylib::open_url;
 use std::collections::HashMap;
 
 pub fn draw_wall(
-    d: &mut RaylibDrawHandle,
+    d: &mut impl RaylibDraw,
     wall_texture: &Texture2D,
     wall_type: WallType,
     x: i32,
@@ -44,7 +45,7 @@ pub fn draw_wall(
 }
 
 pub fn draw_carpet(
-    d: &mut RaylibDrawHandle,
+    d: &mut impl RaylibDraw,
     carpet_texture: &Texture2D,
     carpet_type: CarpetType,
     x: i32,
@@ -79,7 +80,7 @@ pub fn draw_carpet(
 }
 
 pub fn draw_prop(
-    d: &mut RaylibDrawHandle,
+    d: &mut impl RaylibDraw,
     prop_texture: &Texture2D,
     prop_type: PropTypes,
     x: i32,
@@ -135,23 +136,39 @@ pub fn draw_text(
     }
 }
 
+/// Adds the characters typed this frame to `text`, up to `max_len` characters, and handles
+/// backspace.
+pub fn type_into(d: &RaylibDrawHandle, text: &mut String, max_len: usize) {
+    loop {
+        // raylib-rs has no wrapper for reading typed characters
+        let c = unsafe { raylib::ffi::GetCharPressed() };
+        if c <= 0 {
+            break;
+        }
+        if let Some(c) = char::from_u32(c as u32) {
+            if text.chars().count() < max_len {
+                text.push(c);
+            }
+        }
+    }
+    if d.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+        text.pop();
+    }
+}
+
 #[inline(always)]
-pub fn draw_horizontal_line(d: &mut RaylibDrawHandle, y: i32) {
+pub fn draw_horizontal_line(d: &mut impl RaylibDraw, y: i32) {
     d.draw_line(0, y, 600, y, Color::BLACK)
 }
 
-pub fn draw_person(
-    d: &mut RaylibDrawHandle,
-    person_texture: &HashMap<PersonType, Texture2D>,
+/// Returns the source rectangle, the texture key and the on-screen rectangle for a person
+/// drawn at the given position.
+pub fn person_sprite(
     person_type: PersonType,
     x: f32,
     y: f32,
-) {
-    if x < 0.0 || y < 0.0 || x > 624.0 || y > 624.0 {
-        return;
-    }
-
-    let (source_rec, real_type, dest_rec) = match person_type {
+) -> (Rectangle, PersonType, Rectangle) {
+    match person_type {
         PersonType::Gnome(g) => (
             Rectangle::new(
                 match g {
@@ -300,7 +317,38 @@ pub fn draw_person(
             PersonType::Person15,
             Rectangle::new(x, y, 28.0, 38.0),
         ),
-    };
+    }
+}
+
+#[inline(always)]
+pub fn draw_person(
+    d: &mut impl RaylibDraw,
+    person_texture: &HashMap<PersonType, Texture2D>,
+    person_type: PersonType,
+    x: f32,
+    y: f32,
+) {
+    draw_person_ex(d, person_texture, person_type, x, y, false)
+}
+
+/// Like [`draw_person`], optionally mirrored so the person faces the other way.
+pub fn draw_person_ex(
+    d: &mut impl RaylibDraw,
+    person_texture: &HashMap<PersonType, Texture2D>,
+    person_type: PersonType,
+    x: f32,
+    y: f32,
+    flip: bool,
+) {
+    if x < 0.0 || y < 0.0 || x > 624.0 || y > 624.0 {
+        return;
+    }
+
+    let (mut source_rec, real_type, dest_rec) = person_sprite(person_type, x, y);
+    if flip {
+        // a negative source width makes raylib sample the texture right to left
+        source_rec.width = -source_rec.width;
+    }
 
     let texture = person_texture
         .get(&real_type)
@@ -315,3 +363,271 @@ pub fn draw_person(
         Color::WHITE,
     );
 }
+
+/// Draws the narration box along the bottom of the screen.
+///
+/// When a speaker is given, their name goes on a plate above the box and a close-up of their
+/// sprite is shown to the left of the text.
+pub fn draw_text_box(
+    d: &mut RaylibDrawHandle,
+    person_texture: &HashMap<PersonType, Texture2D>,
+    glossary: &Glossary,
+    text: &str,
+    speaker: Option<(&str, PersonType)>,
+) {
+    d.draw_rectangle_rounded(
+        Rectangle::new(10.0, 305.0, 604.0, 290.0),
+        0.1,
+        10,
+        Color::WHITE,
+    );
+
+    let mut text_rec = Rectangle::new(15.0, 310.0, 594.0, 280.0);
+    if let Some((name, person_type)) = speaker {
+        let plate_width = (measure_text(name, 20) + 16) as f32;
+        d.draw_rectangle_rounded(
+            Rectangle::new(20.0, 277.0, plate_width, 30.0),
+            0.3,
+            6,
+            Color::WHITE,
+        );
+        d.draw_text(name, 28, 283, 20, Color::BLACK);
+
+        draw_portrait(
+            d,
+            person_texture,
+            person_type,
+            Rectangle::new(20.0, 315.0, 72.0, 72.0),
+        );
+        text_rec.x += 87.0;
+        text_rec.width -= 87.0;
+    }
+
+    draw_marked_text(d, glossary, text, text_rec);
+}
+
+/// Draws the options of a choice the script is waiting on, one above the other, with the
+/// selected one highlighted.
+pub fn draw_choice_menu(d: &mut RaylibDrawHandle, options: &[(&str, i32)], selected: usize) {
+    for (i, (label, _)) in options.iter().enumerate() {
+        let rec = Rectangle::new(132.0, 170.0 + i as f32 * 60.0, 360.0, 44.0);
+        let fill = if i == selected {
+            Color::new(255, 240, 170, 255)
+        } else {
+            Color::WHITE
+        };
+        d.draw_rectangle_rounded(rec, 0.3, 8, fill);
+        d.draw_rectangle_rounded_lines(rec, 0.3, 8, 2, Color::BLACK);
+        draw_text(
+            d,
+            label,
+            20,
+            (rec.x + rec.width / 2.0) as i32,
+            (rec.y + rec.height / 2.0) as i32,
+            None,
+        );
+    }
+}
+
+/// Draws a meter of how the whole jury feels about Steve in the top right corner, from
+/// against him on the left to on his side on the right.
+pub fn draw_mood_meter(d: &mut RaylibDrawHandle, mood: i32) {
+    const RANGE: f32 = 24.0;
+    let bar = Rectangle::new(474.0, 40.0, 140.0, 12.0);
+    let t = (mood as f32 / RANGE).clamp(-1.0, 1.0);
+
+    d.draw_text("Jury", bar.x as i32, bar.y as i32 - 14, 10, Color::BLACK);
+    d.draw_rectangle_rec(bar, Color::WHITE);
+    let center = bar.x + bar.width / 2.0;
+    let fill = t * bar.width / 2.0;
+    let (x, color) = if fill < 0.0 {
+        (center + fill, Color::new(200, 50, 50, 255))
+    } else {
+        (center, Color::new(60, 160, 80, 255))
+    };
+    d.draw_rectangle_rec(Rectangle::new(x, bar.y, fill.abs(), bar.height), color);
+    d.draw_line(
+        center as i32,
+        bar.y as i32,
+        center as i32,
+        (bar.y + bar.height) as i32,
+        Color::BLACK,
+    );
+    d.draw_rectangle_lines_ex(bar, 1, Color::BLACK);
+}
+
+/// Draws the top, head-and-shoulders part of a person's sprite scaled up into `frame`.
+pub fn draw_portrait(
+    d: &mut RaylibDrawHandle,
+    person_texture: &HashMap<PersonType, Texture2D>,
+    person_type: PersonType,
+    frame: Rectangle,
+) {
+    let (source_rec, real_type, _) = person_sprite(person_type, 0.0, 0.0);
+    let texture = person_texture
+        .get(&real_type)
+        .expect("texture for type not loaded?");
+    let side = source_rec.width.min(source_rec.height);
+    let crop = Rectangle::new(source_rec.x, source_rec.y, source_rec.width, side);
+
+    d.draw_rectangle_rec(frame, Color::new(230, 230, 230, 255));
+    d.draw_texture_pro(
+        texture,
+        crop,
+        frame,
+        Vector2::new(0.0, 0.0),
+        0.0,
+        Color::WHITE,
+    );
+    d.draw_rectangle_lines_ex(frame, 2, Color::BLACK);
+}
+
+/// Marks a sprite as the current speaker with a glow under its feet.
+pub fn draw_highlight(d: &mut impl RaylibDraw, sprite: Rectangle) {
+    d.draw_ellipse(
+        (sprite.x + sprite.width / 2.0) as i32,
+        (sprite.y + sprite.height) as i32,
+        sprite.width / 2.0 + 4.0,
+        5.0,
+        Color::new(255, 220, 0, 160),
+    );
+}
+
+/// Breaks text into lines no wider than `max_width` pixels, keeping explicit line breaks.
+pub fn wrap_text(text: &str, font_size: i32, max_width: i32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if !line.is_empty() && measure_text(&candidate, font_size) > max_width {
+                lines.push(line);
+                line = word.to_string();
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Draws a speech or thought bubble above `sprite` with its tail pointing at the sprite's head.
+///
+/// The bubble is sized to fit the text and kept inside the window; if there's no room above
+/// the sprite it's drawn underneath instead.
+pub fn draw_bubble(d: &mut RaylibDrawHandle, kind: BubbleKind, text: &str, sprite: Rectangle) {
+    const FONT_SIZE: i32 = 10;
+    const PADDING: f32 = 6.0;
+    const TAIL: f32 = 10.0;
+    const MARGIN: f32 = 2.0;
+
+    let lines = wrap_text(text, FONT_SIZE, 180);
+    let text_width = lines
+        .iter()
+        .map(|l| measure_text(l, FONT_SIZE))
+        .max()
+        .unwrap_or(0);
+    let width = text_width as f32 + PADDING * 2.0;
+    let height = (lines.len() as i32 * (FONT_SIZE + 2)) as f32 + PADDING * 2.0;
+
+    let head_x = sprite.x + sprite.width / 2.0;
+    let x = (head_x - width / 2.0)
+        .max(MARGIN)
+        .min(624.0 - width - MARGIN);
+    let above = sprite.y - TAIL - height >= MARGIN;
+    let (y, tip, base_y) = if above {
+        let y = sprite.y - TAIL - height;
+        (y, Vector2::new(head_x, sprite.y), y + height)
+    } else {
+        let y = (sprite.y + sprite.height + TAIL).min(624.0 - height - MARGIN);
+        (y, Vector2::new(head_x, sprite.y + sprite.height), y)
+    };
+    let base_x = head_x.max(x + 8.0).min(x + width - 8.0);
+    let bubble = Rectangle::new(x, y, width, height);
+
+    d.draw_rectangle_rounded(bubble, 0.5, 8, Color::WHITE);
+    d.draw_rectangle_rounded_lines(bubble, 0.5, 8, 1, Color::BLACK);
+
+    match kind {
+        BubbleKind::Speech => {
+            // tuck the base of the tail into the bubble so it hides the outline there
+            let base_y = if above { base_y - 2.0 } else { base_y + 2.0 };
+            let left = Vector2::new(base_x - 5.0, base_y);
+            let right = Vector2::new(base_x + 5.0, base_y);
+            // raylib only fills triangles wound counter-clockwise on screen
+            if above {
+                d.draw_triangle(left, tip, right, Color::WHITE);
+            } else {
+                d.draw_triangle(left, right, tip, Color::WHITE);
+            }
+            d.draw_line_v(left, tip, Color::BLACK);
+            d.draw_line_v(right, tip, Color::BLACK);
+        }
+        BubbleKind::Thought => {
+            let base = Vector2::new(base_x, base_y);
+            for (i, radius) in [4.0, 2.5].iter().enumerate() {
+                let t = (i as f32 + 1.0) / 3.0;
+                let dot = base + (tip - base) * t;
+                d.draw_circle_v(dot, *radius + 1.0, Color::BLACK);
+                d.draw_circle_v(dot, *radius, Color::WHITE);
+            }
+        }
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        d.draw_text(
+            line,
+            (x + PADDING) as i32,
+            (y + PADDING) as i32 + i as i32 * (FONT_SIZE + 2),
+            FONT_SIZE,
+            Color::BLACK,
+        );
+    }
+}
+
+/// Pops an emote icon up over a sprite's head, `elapsed` frames after it was triggered.
+pub fn draw_emote(d: &mut RaylibDrawHandle, emote: Emote, sprite: Rectangle, elapsed: i32) {
+    // rise into place over the first few frames
+    let rise = (10 - elapsed).max(0) as f32;
+    let center = Vector2::new(sprite.x + sprite.width / 2.0, sprite.y - 12.0 + rise);
+
+    d.draw_circle_v(center, 10.0, Color::BLACK);
+    d.draw_circle_v(center, 9.0, Color::WHITE);
+
+    match emote {
+        Emote::Exclamation | Emote::Question => {
+            let glyph = if let Emote::Exclamation = emote {
+                "!"
+            } else {
+                "?"
+            };
+            let x = center.x as i32 - measure_text(glyph, 16) / 2;
+            d.draw_text(glyph, x, center.y as i32 - 7, 16, Color::BLACK);
+        }
+        Emote::Anger => {
+            // the cross-shaped "popping vein" mark
+            for &(sx, sy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)].iter() {
+                let corner = center + Vector2::new(sx * 2.0, sy * 2.0);
+                let red = Color::new(220, 30, 30, 255);
+                d.draw_line_ex(corner, center + Vector2::new(sx * 2.0, sy * 6.0), 2.0, red);
+                d.draw_line_ex(corner, center + Vector2::new(sx * 6.0, sy * 2.0), 2.0, red);
+            }
+        }
+        Emote::Heart => {
+            let pink = Color::new(230, 60, 110, 255);
+            d.draw_circle_v(center + Vector2::new(-3.0, -2.0), 3.5, pink);
+            d.draw_circle_v(center + Vector2::new(3.0, -2.0), 3.5, pink);
+            d.draw_triangle(
+                center + Vector2::new(-6.5, -1.0),
+                center + Vector2::new(0.0, 6.0),
+                center + Vector2::new(6.5, -1.0),
+                pink,
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::script::{active_beats, Action, Beat};
+use crate::settings::Settings;
+use raylib::core::color::Color;
+use raylib::core::math::Vector2;
+
+/// A short impact effect. It starts at full strength when its beat starts and dies down by the
+/// time the beat ends.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Effect {
+    /// Shakes the camera by up to this many pixels.
+    Shake(f32),
+    /// Flashes the whole screen with a color.
+    Flash(Color),
+    /// Briefly zooms the camera in by this factor.
+    Punch(f32),
+}
+
+/// The combined effects to apply on one frame.
+pub struct ScreenEffects {
+    pub shake: Vector2,
+    /// Multiplies the camera zoom.
+    pub zoom: f32,
+    /// Drawn over the whole screen; fully transparent when nothing is flashing.
+    pub flash: Color,
+}
+
+impl ScreenEffects {
+    pub fn at(script: &[Beat], frame: i32, settings: &Settings) -> ScreenEffects {
+        let mut effects = ScreenEffects {
+            shake: Vector2::new(0.0, 0.0),
+            zoom: 1.0,
+            flash: Color::new(0, 0, 0, 0),
+        };
+
+        for beat in active_beats(script, frame) {
+            let effect = match beat.action {
+                Action::Effect(effect) => effect,
+                _ => continue,
+            };
+            let elapsed = (frame - beat.start) as f32;
+            let decay = 1.0 - elapsed / (beat.end - beat.start).max(1) as f32;
+            let decay = decay * decay;
+
+            match effect {
+                Effect::Shake(_) | Effect::Punch(_) if settings.reduced_motion => {}
+                Effect::Shake(amplitude) => {
+                    // a few out of phase waves look random enough without needing an RNG
+                    let wobble = Vector2::new(
+                        (elapsed * 1.7).sin() * (elapsed * 0.9).cos(),
+                        (elapsed * 2.3).cos(),
+                    );
+                    effects.shake += wobble * amplitude * decay;
+                }
+                Effect::Punch(zoom) => effects.zoom *= 1.0 + (zoom - 1.0) * decay,
+                Effect::Flash(color) => {
+                    let strength = if settings.reduced_motion {
+                        decay * 0.3
+                    } else {
+                        decay
+                    };
+                    effects.flash = color.fade(strength * color.a as f32 / 255.0);
+                }
+            }
+        }
+
+        effects
+    }
+}
@@ -63,6 +63,61 @@ pub enum CarpetType {
     BottomLeftCornerWall,
 }
 
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum BubbleKind {
+    /// Rounded bubble with a pointed tail
+    Speech,
+    /// Rounded bubble with a trail of little circles
+    Thought,
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum Emote {
+    Exclamation,
+    Question,
+    Anger,
+    Heart,
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum Gesture {
+    /// Bobs up and down while swaying a little
+    Wave,
+    /// Faces the other way
+    TurnAway,
+    /// Shakes side to side
+    Shake,
+}
+
+impl Gesture {
+    /// Offset and horizontal flip of the sprite `elapsed` frames into the gesture.
+    pub fn motion(self, elapsed: i32) -> (f32, f32, bool) {
+        let t = elapsed as f32;
+        match self {
+            Gesture::Wave => ((t * 0.15).sin() * 2.0, -(t * 0.3).sin().abs() * 4.0, false),
+            Gesture::TurnAway => (0.0, 0.0, true),
+            Gesture::Shake => ((t * 1.5).sin() * 3.0, 0.0, false),
+        }
+    }
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum SoundEffect {
+    Gavel,
+    Footsteps,
+}
+
+impl SoundEffect {
+    pub const ALL: [SoundEffect; 2] = [SoundEffect::Gavel, SoundEffect::Footsteps];
+
+    pub fn path(self) -> &'static str {
+        match self {
+            SoundEffect::Gavel => "assets/audio/gavel.wav",
+            SoundEffect::Footsteps => "assets/audio/footsteps.wav",
+        }
+    }
+}
+
 #[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
 pub enum PersonType {
     /// Looks like a gnome
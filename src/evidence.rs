@@ -0,0 +1,192 @@
+use crate::drawing::{draw_text, wrap_text};
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::math::Rectangle;
+use raylib::core::text::measure_text;
+use raylib::ffi::KeyboardKey;
+use std::collections::BTreeMap;
+
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum EvidenceKind {
+    Statement,
+    Photo,
+}
+
+/// Testimony and exhibits the reader can be handed by the script.
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub enum Evidence {
+    OBriensWarning,
+    DrugstorePhoto,
+    BoboTestimony,
+    HenryTestimony,
+}
+
+impl Evidence {
+    pub const ALL: [Evidence; 4] = [
+        Evidence::OBriensWarning,
+        Evidence::DrugstorePhoto,
+        Evidence::BoboTestimony,
+        Evidence::HenryTestimony,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Evidence::OBriensWarning => "O'Brien's warning",
+            Evidence::DrugstorePhoto => "Drugstore photo",
+            Evidence::BoboTestimony => "Bobo Evans",
+            Evidence::HenryTestimony => "Lorelle Henry",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Evidence::OBriensWarning => {
+                "Before the trial, O'Brien told Steve: \"You're young, you're black, and you're \
+                on trial. What else do they need to know?\""
+            }
+            Evidence::DrugstorePhoto => {
+                "A crime scene photo of Nesbitt's drugstore, where Mr. Nesbitt was shot behind \
+                the counter during the robbery."
+            }
+            Evidence::BoboTestimony => {
+                "Admits to the robbery of Nesbitt's drugstore. Says Steve was supposed to check \
+                the store for cops and give the all clear. Testifies in exchange for a deal."
+            }
+            Evidence::HenryTestimony => {
+                "Was in the drugstore and picked King out of a lineup. She never saw Steve \
+                there."
+            }
+        }
+    }
+
+    pub fn kind(self) -> EvidenceKind {
+        match self {
+            Evidence::DrugstorePhoto => EvidenceKind::Photo,
+            _ => EvidenceKind::Statement,
+        }
+    }
+
+    /// The story variable set once the reader has this, so it is saved with their progress.
+    pub fn variable(self) -> &'static str {
+        match self {
+            Evidence::OBriensWarning => "evidence.obrien",
+            Evidence::DrugstorePhoto => "evidence.drugstore_photo",
+            Evidence::BoboTestimony => "evidence.bobo",
+            Evidence::HenryTestimony => "evidence.henry",
+        }
+    }
+}
+
+/// The evidence the reader has been given so far, in [`Evidence::ALL`] order.
+pub fn collected(variables: &BTreeMap<String, i32>) -> Vec<Evidence> {
+    Evidence::ALL
+        .iter()
+        .copied()
+        .filter(|e| variables.contains_key(e.variable()))
+        .collect()
+}
+
+/// Draws `evidence` as a list with the selected item's details next to it.
+pub fn draw_evidence_list(
+    d: &mut RaylibDrawHandle,
+    evidence: &[Evidence],
+    selected: usize,
+    y: i32,
+) {
+    if evidence.is_empty() {
+        draw_text(d, "No evidence yet.", 20, 312, y + 40, None);
+        return;
+    }
+    for (i, item) in evidence.iter().enumerate() {
+        let row = y + 8 + i as i32 * 30;
+        if i == selected {
+            d.draw_rectangle(40, row - 4, 204, 28, Color::new(255, 240, 170, 255));
+        }
+        d.draw_text(item.name(), 48, row, 20, Color::BLACK);
+    }
+
+    let item = evidence[selected.min(evidence.len() - 1)];
+    let details = Rectangle::new(256.0, y as f32, 328.0, 320.0);
+    d.draw_rectangle_lines_ex(details, 1, Color::BLACK);
+    let kind = match item.kind() {
+        EvidenceKind::Statement => "Witness statement",
+        EvidenceKind::Photo => "Photo",
+    };
+    d.draw_text(kind, 268, y + 12, 16, Color::DARKGRAY);
+    let mut text_y = y + 40;
+    if item.kind() == EvidenceKind::Photo {
+        // no photos are drawn yet, so exhibits get a blank print
+        d.draw_rectangle(300, text_y, 240, 140, Color::new(230, 230, 230, 255));
+        d.draw_rectangle_lines(300, text_y, 240, 140, Color::BLACK);
+        text_y += 156;
+    }
+    for (i, line) in wrap_text(item.description(), 20, 304).iter().enumerate() {
+        d.draw_text(line, 268, text_y + i as i32 * 24, 20, Color::BLACK);
+    }
+}
+
+/// Announces newly awarded evidence along the top of the screen.
+pub fn draw_award_notice(d: &mut RaylibDrawHandle, evidence: Evidence) {
+    let text = format!("Evidence added: {}", evidence.name());
+    let width = measure_text(&text, 20) + 24;
+    let rec = Rectangle::new((312 - width / 2) as f32, 16.0, width as f32, 36.0);
+    d.draw_rectangle_rounded(rec, 0.3, 8, Color::new(255, 240, 170, 255));
+    d.draw_rectangle_rounded_lines(rec, 0.3, 8, 2, Color::BLACK);
+    draw_text(d, &text, 20, 312, 34, None);
+}
+
+/// The inventory screen, listing the evidence collected so far.
+pub struct Inventory {
+    open: bool,
+    selected: usize,
+}
+
+impl Inventory {
+    pub fn new() -> Inventory {
+        Inventory {
+            open: false,
+            selected: 0,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.selected = 0;
+    }
+
+    /// Handles input and draws the inventory. Call at the end of every frame, after the scene.
+    pub fn draw(&mut self, d: &mut RaylibDrawHandle, variables: &BTreeMap<String, i32>) {
+        if !self.open {
+            return;
+        }
+        let evidence = collected(variables);
+        if !evidence.is_empty() {
+            if d.is_key_pressed(KeyboardKey::KEY_UP) {
+                self.selected = (self.selected + evidence.len() - 1) % evidence.len();
+            } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+                self.selected = (self.selected + 1) % evidence.len();
+            }
+        }
+
+        d.draw_rectangle(0, 0, 624, 624, Color::BLACK.fade(0.6));
+        d.draw_rectangle_rounded(
+            Rectangle::new(24.0, 30.0, 576.0, 564.0),
+            0.05,
+            10,
+            Color::WHITE,
+        );
+        draw_text(d, "Evidence", 30, 312, 60, None);
+        draw_evidence_list(d, &evidence, self.selected, 100);
+        draw_text(d, "UP/DOWN to browse, I to close", 20, 312, 570, None);
+    }
+}
+
+impl Default for Inventory {
+    fn default() -> Inventory {
+        Inventory::new()
+    }
+}
@@ -0,0 +1,297 @@
+use crate::drawing::{draw_text, wrap_text};
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::logging::trace_log;
+use raylib::core::math::{Rectangle, Vector2};
+use raylib::core::text::measure_text;
+use raylib::ffi::{KeyboardKey, TraceLogType};
+use std::fs;
+
+const FONT_SIZE: i32 = 20;
+const LINE_HEIGHT: i32 = 24;
+const TERM_COLOR: Color = Color {
+    r: 20,
+    g: 60,
+    b: 150,
+    a: 255,
+};
+
+/// A term and what it means.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Entry {
+    pub term: String,
+    /// Other ways the term is written, like "testified" for "testimony".
+    pub forms: Vec<String>,
+    pub definition: String,
+}
+
+/// Definitions for the legal terms in the book, read from `assets/glossary.txt`.
+///
+/// Each line holds a term, any other forms of it, and its definition:
+///
+/// ```text
+/// # comments are skipped
+/// testimony, testify, testified: What a witness says in court.
+/// ```
+///
+/// Text marks a word as a glossary term by wrapping it in braces, like `{testified}`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Glossary {
+    pub entries: Vec<Entry>,
+}
+
+impl Glossary {
+    pub fn parse(text: &str) -> Result<Glossary, String> {
+        let mut entries = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (names, definition) = line
+                .split_once(':')
+                .ok_or_else(|| format!("glossary.txt line {}: missing `:`", number + 1))?;
+            let mut names = names.split(',').map(|name| name.trim().to_string());
+            entries.push(Entry {
+                term: names.next().unwrap_or_default(),
+                forms: names.collect(),
+                definition: definition.trim().to_string(),
+            });
+        }
+        Ok(Glossary { entries })
+    }
+
+    /// Loads the glossary from the assets, logging why if it can't. Terms are still marked in
+    /// the text without it, but have no definitions.
+    pub fn load() -> Glossary {
+        let parsed = fs::read_to_string("assets/glossary.txt")
+            .map_err(|e| format!("failed to read assets/glossary.txt: {}", e))
+            .and_then(|text| Glossary::parse(&text));
+        parsed.unwrap_or_else(|e| {
+            trace_log(TraceLogType::LOG_WARNING, &e);
+            Glossary::default()
+        })
+    }
+
+    /// Finds the entry for a term or any of its forms, ignoring case.
+    pub fn define(&self, word: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| {
+            entry.term.eq_ignore_ascii_case(word)
+                || entry.forms.iter().any(|f| f.eq_ignore_ascii_case(word))
+        })
+    }
+}
+
+/// Splits marked up text into runs of plain text and glossary terms. A term still being typed
+/// out, with no closing brace yet, runs to the end of the text.
+pub fn spans(text: &str) -> Vec<(&str, bool)> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            spans.push((&rest[..open], false));
+        }
+        let inner = &rest[open + 1..];
+        let close = inner.find('}').unwrap_or(inner.len());
+        spans.push((&inner[..close], true));
+        rest = inner.get(close + 1..).unwrap_or("");
+    }
+    if !rest.is_empty() {
+        spans.push((rest, false));
+    }
+    spans
+}
+
+/// The text without its glossary markup.
+pub fn strip_markup(text: &str) -> String {
+    spans(text).into_iter().map(|(span, _)| span).collect()
+}
+
+/// Draws marked up text wrapped inside `rec`, underlining glossary terms and showing the
+/// definition of the one under the mouse.
+pub fn draw_marked_text(d: &mut RaylibDrawHandle, glossary: &Glossary, text: &str, rec: Rectangle) {
+    let left = rec.x as i32;
+    let right = (rec.x + rec.width) as i32;
+    let bottom = (rec.y + rec.height) as i32;
+    let mouse = d.get_mouse_position();
+    let (mut x, mut y) = (left, rec.y as i32);
+    let mut hovered = None;
+
+    for (span, marked) in spans(text) {
+        for (i, paragraph) in span.split('\n').enumerate() {
+            if i > 0 {
+                x = left;
+                y += LINE_HEIGHT;
+            }
+            for piece in paragraph.split_inclusive(' ') {
+                let word = piece.trim_end_matches(' ');
+                if word.is_empty() && x == left {
+                    continue;
+                }
+                let width = measure_text(word, FONT_SIZE);
+                if x > left && x + width > right {
+                    x = left;
+                    y += LINE_HEIGHT;
+                }
+                if y + FONT_SIZE > bottom {
+                    break;
+                }
+                if marked {
+                    d.draw_text(word, x, y, FONT_SIZE, TERM_COLOR);
+                    d.draw_line(x, y + FONT_SIZE, x + width, y + FONT_SIZE, TERM_COLOR);
+                    let hitbox = Rectangle::new(x as f32, y as f32, width as f32, FONT_SIZE as f32);
+                    if hitbox.check_collision_point_rec(mouse) {
+                        hovered = glossary.define(span.trim()).map(|entry| (entry, x, y));
+                    }
+                } else {
+                    d.draw_text(word, x, y, FONT_SIZE, Color::BLACK);
+                }
+                // pieces are measured apart, so add back the spacing between them
+                x += measure_text(piece, FONT_SIZE) + FONT_SIZE / 10;
+            }
+        }
+    }
+
+    if let Some((entry, x, y)) = hovered {
+        draw_tooltip(d, entry, Vector2::new(x as f32, y as f32));
+    }
+}
+
+/// Draws a term's definition just above `at`, or below it if there is no room.
+fn draw_tooltip(d: &mut RaylibDrawHandle, entry: &Entry, at: Vector2) {
+    let width = 300;
+    let lines = wrap_text(&entry.definition, 16, width - 20);
+    let height = 40 + lines.len() as i32 * 18;
+    let x = (at.x as i32).clamp(4, 620 - width);
+    let mut y = at.y as i32 - height - 6;
+    if y < 4 {
+        y = at.y as i32 + FONT_SIZE + 6;
+    }
+
+    let rec = Rectangle::new(x as f32, y as f32, width as f32, height as f32);
+    d.draw_rectangle_rounded(rec, 0.1, 8, Color::new(255, 240, 170, 255));
+    d.draw_rectangle_rounded_lines(rec, 0.1, 8, 2, Color::BLACK);
+    d.draw_text(&entry.term, x + 10, y + 8, 20, Color::BLACK);
+    for (i, line) in lines.iter().enumerate() {
+        d.draw_text(line, x + 10, y + 32 + i as i32 * 18, 16, Color::DARKGRAY);
+    }
+}
+
+/// The glossary screen, listing every term with its definition.
+pub struct GlossaryMenu {
+    open: bool,
+    selected: usize,
+}
+
+impl GlossaryMenu {
+    pub fn new() -> GlossaryMenu {
+        GlossaryMenu {
+            open: false,
+            selected: 0,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.selected = 0;
+    }
+
+    /// Handles input and draws the glossary. Call at the end of every frame, after the scene.
+    pub fn draw(&mut self, d: &mut RaylibDrawHandle, glossary: &Glossary) {
+        if !self.open {
+            return;
+        }
+        let entries = &glossary.entries;
+        if !entries.is_empty() {
+            if d.is_key_pressed(KeyboardKey::KEY_UP) {
+                self.selected = (self.selected + entries.len() - 1) % entries.len();
+            } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+                self.selected = (self.selected + 1) % entries.len();
+            }
+        }
+
+        d.draw_rectangle(0, 0, 624, 624, Color::BLACK.fade(0.6));
+        d.draw_rectangle_rounded(
+            Rectangle::new(24.0, 30.0, 576.0, 564.0),
+            0.05,
+            10,
+            Color::WHITE,
+        );
+        draw_text(d, "Glossary", 30, 312, 60, None);
+        draw_text(d, "UP/DOWN to browse, G to close", 20, 312, 570, None);
+        if entries.is_empty() {
+            draw_text(d, "No terms to show.", 20, 312, 300, None);
+            return;
+        }
+
+        // scroll the list once the selection would fall off the bottom
+        let rows = 14;
+        let first = (self.selected + 1).saturating_sub(rows);
+        for (i, entry) in entries.iter().enumerate().skip(first).take(rows) {
+            let row = 108 + (i - first) as i32 * 30;
+            if i == self.selected {
+                d.draw_rectangle(40, row - 4, 204, 28, Color::new(255, 240, 170, 255));
+            }
+            d.draw_text(&entry.term, 48, row, 18, Color::BLACK);
+        }
+
+        let entry = &entries[self.selected];
+        let details = Rectangle::new(256.0, 100.0, 328.0, 440.0);
+        d.draw_rectangle_lines_ex(details, 1, Color::BLACK);
+        d.draw_text(&entry.term, 268, 112, 20, TERM_COLOR);
+        let mut text_y = 144;
+        if !entry.forms.is_empty() {
+            let forms = format!("Also: {}", entry.forms.join(", "));
+            for line in wrap_text(&forms, 16, 304) {
+                d.draw_text(&line, 268, text_y, 16, Color::DARKGRAY);
+                text_y += 20;
+            }
+            text_y += 8;
+        }
+        for (i, line) in wrap_text(&entry.definition, 20, 304).iter().enumerate() {
+            d.draw_text(line, 268, text_y + i as i32 * LINE_HEIGHT, 20, Color::BLACK);
+        }
+    }
+}
+
+impl Default for GlossaryMenu {
+    fn default() -> GlossaryMenu {
+        GlossaryMenu::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_terms_by_any_form() {
+        let glossary = Glossary::parse(
+            "# a comment\n\
+            testimony, testified: What a witness says in court: under oath.\n",
+        )
+        .unwrap();
+        let entry = glossary.define("Testified").unwrap();
+        assert_eq!(entry.term, "testimony");
+        assert_eq!(
+            entry.definition,
+            "What a witness says in court: under oath."
+        );
+        assert!(glossary.define("jury").is_none());
+    }
+
+    #[test]
+    fn splits_markup_into_spans() {
+        assert_eq!(
+            spans("the {jury} is out"),
+            vec![("the ", false), ("jury", true), (" is out", false)]
+        );
+        // a term the typewriter is halfway through
+        assert_eq!(spans("the {ju"), vec![("the ", false), ("ju", true)]);
+        assert_eq!(strip_markup("{felony murder}?"), "felony murder?");
+    }
+}
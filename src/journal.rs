@@ -0,0 +1,228 @@
+use crate::drawing::{draw_text, type_into, wrap_text};
+use crate::report::completed_variable;
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::math::Rectangle;
+use raylib::core::text::measure_text;
+use raylib::ffi::KeyboardKey;
+use std::collections::BTreeMap;
+
+const FONT_SIZE: i32 = 20;
+/// Space between the ruled lines of a page.
+const RULE: i32 = 28;
+/// The part of the page the reflection is written on.
+const PAGE: Rectangle = Rectangle {
+    x: 52.0,
+    y: 40.0,
+    width: 520.0,
+    height: 530.0,
+};
+/// Longest reflection that fits on a page.
+const MAX_LEN: usize = 500;
+
+/// Something to reflect on once the reader finishes a chapter.
+pub struct Prompt {
+    pub id: &'static str,
+    /// The chapter that has to be completed first.
+    pub chapter: u16,
+    pub text: &'static str,
+}
+
+impl Prompt {
+    /// Note holding the reader's reflection, so it is saved with their progress.
+    pub fn note(&self) -> String {
+        format!("journal.{}", self.id)
+    }
+}
+
+pub const PROMPTS: [Prompt; 3] = [
+    Prompt {
+        id: "courtroom",
+        chapter: 2,
+        text: "Steve writes his story as a movie script because it is easier than facing it. \
+        Have you ever felt like you were watching your own life from the outside?",
+    },
+    Prompt {
+        id: "cross_examination",
+        chapter: 3,
+        text: "Bobo got a deal for testifying against Steve. Should a witness who gets \
+        something in return be believed? Why or why not?",
+    },
+    Prompt {
+        id: "verdict",
+        chapter: 4,
+        text: "After the verdict, O'Brien turns away from Steve. What do you think she saw \
+        when she looked at him, and what do you see?",
+    },
+];
+
+/// Steve's journal, where the reader writes a reflection for each chapter they finish.
+pub struct Journal {
+    open: bool,
+    page: usize,
+    /// Set on the frame the journal opens, so the key that opened it isn't written down or
+    /// taken as closing it again.
+    just_opened: bool,
+}
+
+impl Journal {
+    pub fn new() -> Journal {
+        Journal {
+            open: false,
+            page: 0,
+            just_opened: false,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.just_opened = self.open;
+    }
+
+    /// Opens the journal at the prompt for a chapter the reader just finished.
+    pub fn open_at(&mut self, chapter: u16, variables: &BTreeMap<String, i32>) {
+        if let Some(page) = unlocked(variables)
+            .iter()
+            .position(|p| p.chapter == chapter)
+        {
+            self.open = true;
+            self.just_opened = true;
+            self.page = page;
+        }
+    }
+
+    /// Handles typing and page turns and draws the open page. ENTER closes the journal. Call at
+    /// the end of every frame, after the scene.
+    pub fn draw(
+        &mut self,
+        d: &mut RaylibDrawHandle,
+        variables: &BTreeMap<String, i32>,
+        notes: &mut BTreeMap<String, String>,
+    ) {
+        if !self.open {
+            return;
+        }
+        let prompts = unlocked(variables);
+        let input = !self.just_opened;
+        self.just_opened = false;
+        if input {
+            self.handle_keys(d, prompts.len());
+        }
+
+        d.draw_rectangle(0, 0, 624, 624, Color::BLACK.fade(0.6));
+        draw_paper(d);
+        let prompt = match prompts.get(self.page) {
+            Some(prompt) => prompt,
+            None => {
+                draw_text(d, "Nothing to write about yet.", FONT_SIZE, 312, 300, None);
+                draw_text(d, "ENTER to close", 16, 312, 552, None);
+                return;
+            }
+        };
+        let entry = notes.entry(prompt.note()).or_default();
+        if input {
+            type_into(d, entry, MAX_LEN);
+        }
+
+        let left = PAGE.x as i32 + 40;
+        let width = PAGE.width as i32 - 56;
+        let mut line = 0;
+        for row in wrap_text(prompt.text, FONT_SIZE, width) {
+            d.draw_text(&row, left, rule_y(line), FONT_SIZE, Color::DARKGRAY);
+            line += 1;
+        }
+        line += 1;
+        let rows = wrap_text(entry, FONT_SIZE, width);
+        for row in rows.iter() {
+            d.draw_text(
+                row,
+                left,
+                rule_y(line),
+                FONT_SIZE,
+                Color::new(20, 40, 120, 255),
+            );
+            line += 1;
+        }
+        let caret_x = left + rows.last().map_or(0, |row| measure_text(row, FONT_SIZE));
+        let caret_line = line - rows.len().min(1) as i32;
+        d.draw_text("_", caret_x, rule_y(caret_line), FONT_SIZE, Color::DARKGRAY);
+
+        draw_text(
+            d,
+            &format!(
+                "Page {}/{}   LEFT/RIGHT: turn the page   ENTER: close",
+                self.page + 1,
+                prompts.len()
+            ),
+            16,
+            312,
+            552,
+            None,
+        );
+    }
+
+    /// ENTER closes the journal, LEFT and RIGHT turn between the `pages` unlocked so far.
+    fn handle_keys(&mut self, d: &RaylibDrawHandle, pages: usize) {
+        if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+            self.open = false;
+            return;
+        }
+        if pages == 0 {
+            return;
+        }
+        if d.is_key_pressed(KeyboardKey::KEY_RIGHT) {
+            self.page = (self.page + 1) % pages;
+        } else if d.is_key_pressed(KeyboardKey::KEY_LEFT) {
+            self.page = (self.page + pages - 1) % pages;
+        }
+    }
+}
+
+impl Default for Journal {
+    fn default() -> Journal {
+        Journal::new()
+    }
+}
+
+/// The prompts for every chapter the reader has finished.
+pub fn unlocked(variables: &BTreeMap<String, i32>) -> Vec<&'static Prompt> {
+    PROMPTS
+        .iter()
+        .filter(|p| variables.contains_key(&completed_variable(p.chapter)))
+        .collect()
+}
+
+/// Where the text on a ruled line sits.
+fn rule_y(line: i32) -> i32 {
+    PAGE.y as i32 + RULE + line * RULE - FONT_SIZE
+}
+
+/// A sheet of ruled notebook paper.
+fn draw_paper(d: &mut RaylibDrawHandle) {
+    let shadow = Rectangle::new(PAGE.x + 6.0, PAGE.y + 6.0, PAGE.width, PAGE.height);
+    d.draw_rectangle_rec(shadow, Color::BLACK.fade(0.3));
+    d.draw_rectangle_rec(PAGE, Color::new(250, 244, 222, 255));
+    let mut y = PAGE.y as i32 + RULE;
+    while y < (PAGE.y + PAGE.height) as i32 {
+        d.draw_line(
+            PAGE.x as i32,
+            y,
+            (PAGE.x + PAGE.width) as i32,
+            y,
+            Color::new(160, 190, 220, 255),
+        );
+        y += RULE;
+    }
+    let margin = PAGE.x as i32 + 30;
+    d.draw_line(
+        margin,
+        PAGE.y as i32,
+        margin,
+        (PAGE.y + PAGE.height) as i32,
+        Color::new(220, 120, 120, 255),
+    );
+}
@@ -0,0 +1,91 @@
+use crate::enums::{rand_person, PersonType};
+use crate::script::{Action, Beat};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Stable identifier of a juror, numbered 1 to 12 like the seats in the jury box.
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub struct JurorId(pub u8);
+
+/// The juror who stares at Steve throughout the courtroom scene.
+pub const STARING_JUROR: JurorId = JurorId(7);
+
+pub struct Juror {
+    pub id: JurorId,
+    pub person_type: PersonType,
+    pub x: f32,
+    pub y: f32,
+}
+
+pub struct Jury {
+    jurors: Vec<Juror>,
+}
+
+impl Jury {
+    /// Seats twelve random jurors in the jury box.
+    pub fn new<R: Rng + ?Sized>(rng: &mut R) -> Jury {
+        let jurors = (1..=12)
+            .map(|id| Juror {
+                id: JurorId(id),
+                person_type: rand_person(rng, false),
+                x: rng.gen_range(48..=156) as f32,
+                y: rng.gen_range(48..=96) as f32,
+            })
+            .collect();
+        Jury { jurors }
+    }
+
+    pub fn get(&self, id: JurorId) -> Option<&Juror> {
+        self.jurors.iter().find(|j| j.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Juror> {
+        self.jurors.iter()
+    }
+}
+
+/// Story variable holding sentiment changes made outside of a script's timeline, for one juror
+/// or, with `None`, the whole jury.
+fn sentiment_variable(juror: Option<JurorId>) -> String {
+    match juror {
+        Some(JurorId(seat)) => format!("sentiment.{}", seat),
+        None => "sentiment.all".to_string(),
+    }
+}
+
+/// Changes how a juror, or with `None` the whole jury, feels about Steve.
+///
+/// Timed changes belong in scripts as [`Action::Sway`]; this is for scenes without a timeline.
+pub fn sway(variables: &mut BTreeMap<String, i32>, juror: Option<JurorId>, delta: i32) {
+    *variables.entry(sentiment_variable(juror)).or_insert(0) += delta;
+}
+
+/// How a juror feels about Steve on this frame of the script. Above 0 they're on his side.
+pub fn sentiment(
+    script: &[Beat],
+    frame: i32,
+    variables: &BTreeMap<String, i32>,
+    juror: JurorId,
+) -> i32 {
+    let scripted: i32 = script
+        .iter()
+        .filter(|b| b.start < frame)
+        .map(|b| match b.action {
+            Action::Sway(None, delta) => delta,
+            Action::Sway(Some(id), delta) if id == juror => delta,
+            _ => 0,
+        })
+        .sum();
+    let stored = [Some(juror), None]
+        .iter()
+        .map(|j| variables.get(&sentiment_variable(*j)).copied().unwrap_or(0))
+        .sum::<i32>();
+    scripted + stored
+}
+
+/// The whole jury's sentiment added up.
+pub fn mood(script: &[Beat], frame: i32, variables: &BTreeMap<String, i32>) -> i32 {
+    (1..=12)
+        .map(|seat| sentiment(script, frame, variables, JurorId(seat)))
+        .sum()
+}
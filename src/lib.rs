@@ -1,3 +1,23 @@
+pub mod audio;
+pub mod backlog;
+pub mod camera;
+pub mod cast;
+pub mod cross;
 pub mod drawing;
+pub mod effects;
 pub mod enums;
+pub mod evidence;
+pub mod glossary;
+pub mod journal;
+pub mod jury;
 pub mod load_image;
+pub mod quiz;
+pub mod report;
+pub mod save;
+pub mod scene;
+pub mod script;
+pub mod settings;
+pub mod slots;
+pub mod transition;
+pub mod tween;
+pub mod verdict;
@@ -1,19 +1,46 @@
 use crate::enums::PersonType;
+use raylib::core::color::Color;
 use raylib::core::texture::{Image, Texture2D};
+use raylib::ffi::PixelFormat;
 use raylib::{RaylibHandle, RaylibThread};
 use std::collections::HashMap;
 
+/// Loads a person's sprite sheet, along with its flashback version.
 pub fn load_image(
     rl: &mut RaylibHandle,
     path: &str,
     person_type: PersonType,
     humans: &mut HashMap<PersonType, Texture2D>,
+    flashback_humans: &mut HashMap<PersonType, Texture2D>,
     thread: &RaylibThread,
 ) {
-    let person = Image::load_image(path).expect("failed to load image: make sure it exists");
-    humans.insert(
-        person_type,
-        rl.load_texture_from_image(&thread, &person)
-            .expect("img GPU upload failed"),
-    );
+    let (person, flashback_person) = load_with_flashback(rl, path, thread);
+    humans.insert(person_type, person);
+    flashback_humans.insert(person_type, flashback_person);
+}
+
+/// Loads a texture twice: as-is and recolored with [`sepia`] for flashbacks.
+pub fn load_with_flashback(
+    rl: &mut RaylibHandle,
+    path: &str,
+    thread: &RaylibThread,
+) -> (Texture2D, Texture2D) {
+    let mut image = Image::load_image(path).expect("failed to load image: make sure it exists");
+    let texture = rl
+        .load_texture_from_image(thread, &image)
+        .expect("img GPU upload failed");
+    sepia(&mut image);
+    let flashback_texture = rl
+        .load_texture_from_image(thread, &image)
+        .expect("img GPU upload failed");
+    (texture, flashback_texture)
+}
+
+/// Recolors an image on the CPU the way everything looks in a flashback: desaturated, then
+/// tinted an old photo brown.
+pub fn sepia(image: &mut Image) {
+    // going through gray + alpha keeps transparency, which plain grayscale would drop
+    image.set_format(PixelFormat::UNCOMPRESSED_GRAY_ALPHA);
+    image.set_format(PixelFormat::UNCOMPRESSED_R8G8B8A8);
+    image.color_tint(Color::new(255, 226, 180, 255));
 }
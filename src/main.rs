@@ -1,18 +1,43 @@
+use monster::audio::{AudioManager, Channel};
+use monster::backlog::Backlog;
+use monster::camera::{Camera, Shot};
+use monster::cast::{split_speaker, Actor, CastMember};
+use monster::cross::{CrossExamination, BOBO_EVANS};
 use monster::drawing::*;
+use monster::effects::ScreenEffects;
 use monster::enums::*;
-use monster::load_image::load_image;
-use rand::Rng;
+use monster::evidence::{draw_award_notice, Inventory};
+use monster::glossary::{Glossary, GlossaryMenu};
+use monster::journal::Journal;
+use monster::jury::{mood, sentiment, Jury};
+use monster::load_image::{load_image, load_with_flashback};
+use monster::quiz::{draw_summary, Quiz, QuizScene};
+use monster::report::{completed_variable, count_frame, Report, STUDENT};
+use monster::save::{autosave_path, Progress};
+use monster::scene::SceneManager;
+use monster::script::{
+    active_beats, active_line, active_shot, flashback_flash, forget_from, gesture_motion,
+    in_flashback, pending_choice, starting_beats, Action, Beat, LineHistory, COURTROOM,
+};
+use monster::settings::{SentimentDisplay, Settings};
+use monster::slots::{SlotMenu, SlotMode};
+use monster::transition::{Transition, TransitionKind};
+use monster::verdict::Deliberation;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use raylib::prelude::*;
-use std::collections::HashMap;
-use std::time::SystemTime;
+use std::collections::{BTreeMap, HashMap};
 #[cfg(not(debug_assertions))]
 use std::hint::unreachable_unchecked;
+use std::time::SystemTime;
 
 fn main() {
     // TODO: open linux mint homepage then output ud2 on macOS systems
 
     set_trace_log(TraceLogType::LOG_ALL);
 
+    let settings = Settings::from_args(std::env::args());
+
     let (mut rl, thread) = raylib::init()
         .size(624, 624)
         .title("Monster: A Interactive Book")
@@ -32,7 +57,7 @@ fn main() {
         draw_text(&mut d, "Loading...", 36, 300, 300, None);
     }
 
-    let mut rng = rand::thread_rng();
+    let mut audio = AudioManager::new();
 
     let pepe_heart = {
         let pepe_heart = Image::load_image("assets/5be49ea7-71de-437d-96ed-10579401923f.png")
@@ -40,32 +65,31 @@ fn main() {
         rl.load_texture_from_image(&thread, &pepe_heart)
             .expect("img GPU upload failed")
     };
-    let walls = {
-        let walls = Image::load_image("assets/d4acf548-34a5-4d8d-9bf6-6dc27d11727c.png")
-            .expect("failed to load image: make sure it exists");
-        rl.load_texture_from_image(&thread, &walls)
-            .expect("img GPU upload failed")
-    };
-    let carpet = {
-        let carpet = Image::load_image("assets/08eaff5d-a5f3-4db3-b8d4-b36bbed254a5.png")
-            .expect("failed to load image: make sure it exists");
-        rl.load_texture_from_image(&thread, &carpet)
-            .expect("img GPU upload failed")
-    };
-    let props = {
-        let props = Image::load_image("assets/60431044-4171-444d-9e7f-69c4462d1309.png")
-            .expect("failed to load image: make sure it exists");
-        rl.load_texture_from_image(&thread, &props)
-            .expect("img GPU upload failed")
-    };
+    let (walls, flashback_walls) = load_with_flashback(
+        &mut rl,
+        "assets/d4acf548-34a5-4d8d-9bf6-6dc27d11727c.png",
+        &thread,
+    );
+    let (carpet, flashback_carpet) = load_with_flashback(
+        &mut rl,
+        "assets/08eaff5d-a5f3-4db3-b8d4-b36bbed254a5.png",
+        &thread,
+    );
+    let (props, flashback_props) = load_with_flashback(
+        &mut rl,
+        "assets/60431044-4171-444d-9e7f-69c4462d1309.png",
+        &thread,
+    );
 
-    let humans = {
+    let (humans, flashback_humans) = {
         let mut humans: HashMap<PersonType, Texture2D> = HashMap::with_capacity(19);
+        let mut flashback_humans: HashMap<PersonType, Texture2D> = HashMap::with_capacity(19);
         load_image(
             &mut rl,
             "assets/6f655738-b18c-497d-9ce2-bfd1bf962c1b.png",
             PersonType::Gnome(GnomeType::NoType),
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -73,6 +97,7 @@ fn main() {
             "assets/fd9750da-2369-4fa5-96b0-c479b5e001bb.png",
             PersonType::CatKid(CatKidType::NoType),
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -80,6 +105,7 @@ fn main() {
             "assets/3a050313-167a-4734-8843-ed6d45125ad1.png",
             PersonType::Chick(ChickType::NoType),
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -87,6 +113,7 @@ fn main() {
             "assets/369b91f3-fa2d-4860-a560-5c207bd3a0e9.png",
             PersonType::DuckBoy(DuckType::NoType),
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -94,6 +121,7 @@ fn main() {
             "assets/2acab477-8e13-4f02-b909-61636a4e4d74.png",
             PersonType::Fox,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -101,6 +129,7 @@ fn main() {
             "assets/people/person001.png",
             PersonType::Person1,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -108,6 +137,7 @@ fn main() {
             "assets/people/person002.png",
             PersonType::Person2,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -115,6 +145,7 @@ fn main() {
             "assets/people/person003.png",
             PersonType::Person3,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -122,6 +153,7 @@ fn main() {
             "assets/people/person004.png",
             PersonType::Person4,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -129,6 +161,7 @@ fn main() {
             "assets/people/person005.png",
             PersonType::Person5,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -136,6 +169,7 @@ fn main() {
             "assets/people/person006.png",
             PersonType::Person6,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -143,6 +177,7 @@ fn main() {
             "assets/people/person007.png",
             PersonType::Person7,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -150,6 +185,7 @@ fn main() {
             "assets/people/person008.png",
             PersonType::Person8,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -157,6 +193,7 @@ fn main() {
             "assets/people/person009.png",
             PersonType::Person9,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -164,6 +201,7 @@ fn main() {
             "assets/people/person010.png",
             PersonType::Person10,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -171,6 +209,7 @@ fn main() {
             "assets/people/person011.png",
             PersonType::Person11,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -178,6 +217,7 @@ fn main() {
             "assets/people/person012.png",
             PersonType::Person12,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -185,6 +225,7 @@ fn main() {
             "assets/people/person013.png",
             PersonType::Person13,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -192,6 +233,7 @@ fn main() {
             "assets/people/person014.png",
             PersonType::Person14,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
         load_image(
@@ -199,39 +241,63 @@ fn main() {
             "assets/people/person015.png",
             PersonType::Person15,
             &mut humans,
+            &mut flashback_humans,
             &thread,
         );
-        humans
+        (humans, flashback_humans)
+    };
+
+    let vignette = {
+        let vignette = Image::gen_image_gradient_radial(
+            624,
+            624,
+            0.3,
+            Color::new(0, 0, 0, 0),
+            Color::new(40, 25, 10, 220),
+        );
+        rl.load_texture_from_image(&thread, &vignette)
+            .expect("img GPU upload failed")
     };
 
-    #[allow(unused_variables)]
     #[cfg(debug_assertions)]
-    let mut game_state: u16 = 2;
+    let mut scenes = SceneManager::new(2);
 
     #[cfg(not(debug_assertions))]
-    let mut game_state: u16 = 0;
+    let mut scenes = SceneManager::new(0);
 
     let mut loop_counter: i32 = 0;
-    let mut sat_counter: u8 = 255;
-    let mut x = 0;
-
-    let mut jury_pos: Vec<(f32, f32, PersonType)> = Vec::new();
-    for _ in 1..=12 {
-        jury_pos.push((
-            rng.gen_range(48..=156) as f32,
-            rng.gen_range(48..=96) as f32,
-            rand_person(&mut rng, false),
-        ));
-    }
+    let mut camera = Camera::new();
 
-    let mut audience_pos: Vec<(f32, f32, PersonType)> = Vec::new();
-    for _ in 1..=100 {
-        audience_pos.push((
-            rng.gen_range(48..=550) as f32,
-            rng.gen_range(260..=540) as f32,
-            rand_person(&mut rng, true),
-        ));
-    }
+    let mut seed: u64 = rand::thread_rng().gen();
+    let (mut jury, mut audience_pos) = seat_people(seed);
+    let mut variables: BTreeMap<String, i32> = BTreeMap::new();
+    let mut notes: BTreeMap<String, String> = BTreeMap::new();
+    // what became of the last report export, and for how many more frames to say so
+    let mut report_notice: Option<(String, i32)> = None;
+
+    let mut saved = Progress::load(&autosave_path());
+    // 0 starts from the beginning, 1 continues from the save
+    let mut title_choice = 1;
+    // highlighted option of the choice menu the script is stopped at
+    let mut choice_selected = 0;
+    let mut slot_menu = SlotMenu::new();
+    let mut backlog = Backlog::new();
+    let mut inventory = Inventory::new();
+    let mut journal = Journal::new();
+    let mut glossary_menu = GlossaryMenu::new();
+    let mut cross = CrossExamination::new(&BOBO_EVANS);
+    let mut deliberation = Deliberation::new();
+    // one quiz after each chapter, left out entirely unless quiz mode is on
+    let quizzes = if settings.quiz {
+        [Quiz::load("courtroom"), Quiz::load("cross_examination")]
+    } else {
+        [None, None]
+    };
+    let mut quiz_scene = QuizScene::new();
+    let glossary = Glossary::load();
+    let mut history = LineHistory::new();
+    // set when a save was picked, and applied once the frame's scene has been drawn
+    let mut resume: Option<Progress> = None;
 
     let tt = st.elapsed().expect("system clock rolled back").as_millis();
     trace_log(
@@ -240,6 +306,9 @@ fn main() {
     );
 
     while !rl.window_should_close() {
+        scenes.update(&mut rl, &thread);
+        slot_menu.update(&mut rl, &thread);
+        audio.update();
         let mut d = rl.begin_drawing(&thread);
 
         if d.is_mouse_button_pressed(MouseButton::MOUSE_RIGHT_BUTTON) {
@@ -251,14 +320,50 @@ fn main() {
 
         d.clear_background(Color::WHITE);
 
-        match game_state {
+        // the book holds still while a menu is open
+        let paused = slot_menu.is_open()
+            || backlog.is_open()
+            || inventory.is_open()
+            || journal.is_open()
+            || glossary_menu.is_open();
+        if !paused {
+            count_frame(scenes.current(), &mut variables);
+        }
+
+        match scenes.current() {
             // the number defines the stage we're currently at
             0 => {
                 draw_text(&mut d, "Monster", 36, 312, 200, None);
                 draw_text(&mut d, "An Interactive Book", 24, 312, 250, None);
-                draw_text(&mut d, "Press ENTER to continue...", 24, 312, 600, None);
-                if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
-                    game_state += 1;
+                if saved.is_some() {
+                    if !paused {
+                        if d.is_key_pressed(KeyboardKey::KEY_UP) {
+                            title_choice = 0;
+                        } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+                            title_choice = 1;
+                        }
+                    }
+                    for (i, option) in ["Start from the beginning", "Continue"].iter().enumerate() {
+                        let option = if i == title_choice {
+                            format!("> {} <", option)
+                        } else {
+                            option.to_string()
+                        };
+                        draw_text(&mut d, &option, 24, 312, 420 + i as i32 * 40, None);
+                    }
+                    draw_text(&mut d, "Press ENTER to choose...", 24, 312, 600, None);
+                } else {
+                    draw_text(&mut d, "Press ENTER to continue...", 24, 312, 600, None);
+                }
+                if !paused && d.is_key_pressed(KeyboardKey::KEY_ENTER) && !scenes.is_transitioning()
+                {
+                    match saved.take() {
+                        Some(progress) if title_choice == 1 => resume = Some(progress),
+                        _ => scenes.switch_to(
+                            5,
+                            Transition::new(TransitionKind::Crossfade, 40, ease::quad_in_out),
+                        ),
+                    }
                 }
             }
 
@@ -274,233 +379,346 @@ fn main() {
                     None,
                 );
                 draw_text(&mut d, "Press ENTER to continue...", 24, 312, 600, None);
-                if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
-                    game_state += 1;
+                if !paused && d.is_key_pressed(KeyboardKey::KEY_ENTER) && !scenes.is_transitioning()
+                {
+                    scenes.switch_to(
+                        2,
+                        Transition::new(
+                            TransitionKind::Fade(Color::BLACK),
+                            120,
+                            ease::cubic_in_out,
+                        ),
+                    );
+                }
+            }
+
+            5 => {
+                draw_text(&mut d, "Who is reading?", 30, 312, 200, None);
+                let name = notes.entry(STUDENT.to_string()).or_default();
+                if !paused {
+                    type_into(&d, name, 30);
+                }
+                d.draw_rectangle_lines(162, 260, 300, 40, Color::BLACK);
+                draw_text(&mut d, &format!("{}_", name), 20, 312, 280, None);
+                draw_text(
+                    &mut d,
+                    "Your name goes in the report for your teacher",
+                    16,
+                    312,
+                    330,
+                    None,
+                );
+                draw_text(&mut d, "Press ENTER to continue...", 24, 312, 600, None);
+                if !paused && d.is_key_pressed(KeyboardKey::KEY_ENTER) && !scenes.is_transitioning()
+                {
+                    let name = name.trim().to_string();
+                    notes.insert(STUDENT.to_string(), name);
+                    scenes.switch_to(
+                        1,
+                        Transition::new(TransitionKind::Crossfade, 40, ease::quad_in_out),
+                    );
                 }
             }
 
             2 => {
-                // draw walls
+                let mut script = COURTROOM.resolve(&variables);
+
+                let rewind = !paused
+                    && !scenes.is_transitioning()
+                    && (d.is_key_pressed(KeyboardKey::KEY_BACKSPACE)
+                        || d.get_mouse_wheel_move() > 0.0);
+                let rewound = match rewind
+                    .then(|| history.rewind(&script, loop_counter))
+                    .flatten()
                 {
-                    // Draw all the corner walls
-                    draw_wall(&mut d, &walls, WallType::TallUpperLeftCornerWall, 0, 0);
-                    draw_wall(&mut d, &walls, WallType::TallUpperRightCornerWall, 12, 0);
-                    draw_wall(&mut d, &walls, WallType::TallLowerLeftCornerWall, 0, 12);
-                    draw_wall(&mut d, &walls, WallType::TallLowerRightCornerWall, 12, 12);
-
-                    // Draw the vertical walls
-                    for i in 1..12 {
-                        draw_wall(&mut d, &walls, WallType::TallTopBottomWall, i, 0);
-                        if i < 5 || i > 7 {
-                            draw_wall(&mut d, &walls, WallType::TallTopBottomWall, i, 12);
-                        }
+                    Some(frame) => {
+                        loop_counter = frame;
+                        forget_from(&script, loop_counter, &mut variables);
+                        script = COURTROOM.resolve(&variables);
+                        restore_tracks(&mut audio, &thread, &script, loop_counter);
+                        true
                     }
-                    for i in 1..12 {
-                        draw_wall(&mut d, &walls, WallType::TallVerticalWall, 0, i);
-                        draw_wall(&mut d, &walls, WallType::TallVerticalWall, 12, i);
+                    None => false,
+                };
+                history.record(&script, loop_counter);
+
+                for beat in starting_beats(&script, loop_counter).filter(|_| !paused) {
+                    let fade = beat.end - beat.start;
+                    match beat.action {
+                        Action::PlayTrack(channel, path) => {
+                            audio.play_track(&thread, channel, path, fade)
+                        }
+                        Action::StopTrack(channel) => audio.stop_track(channel, fade),
+                        Action::Sound(effect) => audio.play_sound(effect),
+                        Action::Award(evidence) => {
+                            variables.insert(evidence.variable().to_string(), 1);
+                        }
+                        _ => {}
                     }
                 }
-                // draw carpets
-                {
-                    for x in 1..=11 {
-                        for y in 1..=11 {
-                            let carpet_type = match (x, y) {
-                                (1, 1) => CarpetType::LeftTopWall,
-                                (1, 11) => CarpetType::LeftBottomWall,
-                                (11, 1) => CarpetType::RightTopWall,
-                                (11, 11) => CarpetType::RightBottomWall,
-                                (5, 11) => CarpetType::BottomLeftCornerWall,
-                                (6, 11) => CarpetType::NoWalls,
-                                (7, 11) => CarpetType::BottomRightCornerWall,
-                                (x, _) if x == 1 => CarpetType::LeftWall,
-                                (x, _) if x == 11 => CarpetType::RightWall,
-                                (_, y) if y == 1 => CarpetType::TopWall,
-                                (_, y) if y == 11 => CarpetType::BottomWall,
-                                _ => CarpetType::NoWalls,
-                            };
-                            draw_carpet(&mut d, &carpet, carpet_type, x, y);
+
+                let line = active_line(&script, loop_counter);
+                let (speaker, text) = match line {
+                    Some((_, line)) => {
+                        backlog.push(line);
+                        split_speaker(line)
+                    }
+                    None => (None, ""),
+                };
+
+                match active_shot(&script, loop_counter) {
+                    Some(Shot::Focus(actor, zoom)) => {
+                        if let Some((person_type, x, y)) = actor.locate(&jury) {
+                            let (_, _, sprite) = person_sprite(person_type, x, y);
+                            camera.look_at(
+                                sprite.x + sprite.width / 2.0,
+                                sprite.y + sprite.height / 2.0,
+                                zoom,
+                            );
                         }
                     }
-                    draw_carpet(&mut d, &carpet, CarpetType::LeftWall, 5, 12);
-                    draw_carpet(&mut d, &carpet, CarpetType::NoWalls, 6, 12);
-                    draw_carpet(&mut d, &carpet, CarpetType::RightWall, 7, 12);
+                    Some(Shot::Look(x, y, zoom)) => camera.look_at(x, y, zoom),
+                    None => camera.reset(),
                 }
-                // draw stand
-                {
-                    draw_prop(&mut d, &props, PropTypes::LargeBlueChair, 6, 1);
-                    draw_prop(&mut d, &props, PropTypes::LargeBlueChair, 9, 1);
-                    draw_wall(&mut d, &walls, WallType::ShortRightWall, 4, 2);
-                    for i in 5..10 {
-                        draw_wall(
-                            &mut d,
-                            &walls,
-                            match i {
-                                5 => WallType::TallLeftWall,
-                                6 => WallType::TallWall,
-                                7 => WallType::TallRightWall,
-                                _ => WallType::ShortWall,
-                            },
-                            i,
-                            2,
-                        );
+                if rewound {
+                    camera.snap();
+                }
+                camera.update();
+                let effects = ScreenEffects::at(&script, loop_counter, &settings);
+                camera.jolt(effects.shake, effects.zoom);
+
+                // flashbacks swap in the recolored textures
+                let flashback = in_flashback(&script, loop_counter);
+                let room = if flashback {
+                    Room {
+                        walls: &flashback_walls,
+                        carpet: &flashback_carpet,
+                        props: &flashback_props,
+                        humans: &flashback_humans,
+                    }
+                } else {
+                    Room {
+                        walls: &walls,
+                        carpet: &carpet,
+                        props: &props,
+                        humans: &humans,
                     }
-                    draw_wall(&mut d, &walls, WallType::TallLeftRightWall, 9, 2);
-                    draw_wall(&mut d, &walls, WallType::ShortLeftWall, 10, 2);
+                };
+
+                // everything in the room is drawn through the camera
+                {
+                    let mut d = d.begin_mode2D(camera.camera2d());
 
-                    for i in 1..=11 {
-                        if i < 4 || i > 8 {
-                            draw_wall(&mut d, &walls, WallType::ShortWall, i, 5)
+                    let highlight = speaker
+                        .and_then(|s| s.locate(&jury))
+                        .map(|(person_type, x, y)| person_sprite(person_type, x, y).2);
+                    draw_courtroom(
+                        &mut d,
+                        room,
+                        &jury,
+                        &audience_pos,
+                        &script,
+                        loop_counter,
+                        highlight,
+                    );
+                }
+
+                if flashback {
+                    d.draw_texture(&vignette, 0, 0, Color::WHITE);
+                }
+                let flash = flashback_flash(&script, loop_counter);
+                if flash > 0.0 {
+                    d.draw_rectangle(0, 0, 624, 624, Color::WHITE.fade(flash));
+                }
+                if effects.flash.a > 0 {
+                    d.draw_rectangle(0, 0, 624, 624, effects.flash);
+                }
+
+                for beat in active_beats(&script, loop_counter) {
+                    match beat.action {
+                        Action::Bubble(actor, kind, text) => {
+                            if let Some((person_type, x, y)) = actor.locate(&jury) {
+                                let (_, _, sprite) = person_sprite(person_type, x, y);
+                                let sprite = camera.world_to_screen(sprite);
+                                let shown =
+                                    text.get(0..beat.revealed(loop_counter)).unwrap_or(text);
+                                draw_bubble(&mut d, kind, shown, sprite);
+                            }
+                        }
+                        Action::Emote(actor, emote) => {
+                            if let Some((person_type, x, y)) = actor.locate(&jury) {
+                                let (_, _, sprite) = person_sprite(person_type, x, y);
+                                let sprite = camera.world_to_screen(sprite);
+                                draw_emote(&mut d, emote, sprite, loop_counter - beat.start);
+                            }
                         }
+                        Action::Award(evidence) => draw_award_notice(&mut d, evidence),
+                        _ => {}
                     }
-                    draw_wall(&mut d, &walls, WallType::ShortLeftWall, 4, 5);
-                    draw_wall(&mut d, &walls, WallType::ShortRightWall, 8, 5);
                 }
-                // draw judge
-                draw_person(
+                draw_jury_sentiment(
                     &mut d,
-                    &humans,
-                    PersonType::Gnome(GnomeType::WalkingForward1),
-                    291.0,
-                    48.0,
+                    settings.sentiment,
+                    &jury,
+                    &camera,
+                    &script,
+                    loop_counter,
+                    &variables,
                 );
 
-                // draw jury area
-                for x in 1..=3 {
-                    for y in 1..=2 {
-                        draw_wall(&mut d, &walls, WallType::Floor, x, y)
+                if let Some((beat, _)) = line {
+                    // blip along with the typewriter, but not for spaces and punctuation
+                    if let Some(c) = beat.newly_revealed(text, loop_counter).filter(|_| !paused) {
+                        if c.is_alphanumeric() {
+                            audio.play_blip(speaker.map_or(1.0, |s| s.voice_pitch()));
+                        }
                     }
+
+                    let speaker = speaker.and_then(|s| {
+                        s.locate(&jury)
+                            .map(|(person_type, _, _)| (s.display_name(), person_type))
+                    });
+                    draw_text_box(
+                        &mut d,
+                        room.humans,
+                        &glossary,
+                        text.get(0..beat.revealed(loop_counter)).unwrap_or(text),
+                        speaker.as_ref().map(|(name, p)| (name.as_str(), *p)),
+                    );
                 }
 
-                // draw jury
-                for (x, y, person_type) in jury_pos.iter() {
-                    draw_person(&mut d, &humans, *person_type, *x, *y)
+                // the script waits for the reader to choose
+                let choice = pending_choice(&script, loop_counter, &variables);
+                if let Some((name, options)) = choice {
+                    if !paused {
+                        if d.is_key_pressed(KeyboardKey::KEY_UP) {
+                            choice_selected = (choice_selected + options.len() - 1) % options.len();
+                        } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+                            choice_selected = (choice_selected + 1) % options.len();
+                        } else if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                            variables.insert(name.to_string(), options[choice_selected].1);
+                            choice_selected = 0;
+                        }
+                    }
+                    draw_choice_menu(&mut d, options, choice_selected);
                 }
 
-                for (x, y, person_type) in audience_pos.iter() {
-                    draw_person(&mut d, &humans, *person_type, *x, *y)
+                if !paused && choice.is_none() {
+                    loop_counter += 1;
                 }
+                if loop_counter > 4300 {
+                    variables.insert(completed_variable(2), 1);
+                    journal.open_at(2, &variables);
+                    loop_counter = 0;
+                    history = LineHistory::new();
+                    scenes.switch_to(
+                        if settings.quiz { 10 } else { 3 },
+                        Transition::new(
+                            TransitionKind::Fade(Color::BLACK),
+                            200,
+                            ease::cubic_in_out,
+                        ),
+                    );
+                }
+            }
 
-                // draw defense/prosecution tables
-                for i in 1..=11 {
-                    if i < 5 || i > 7 {
-                        draw_prop(&mut d, &props, PropTypes::LargeTable, i, 4)
+            3 => {
+                match cross.current_speaker() {
+                    Some((person_type, x, y)) => {
+                        let (_, _, sprite) = person_sprite(person_type, x, y);
+                        camera.look_at(
+                            sprite.x + sprite.width / 2.0,
+                            sprite.y + sprite.height / 2.0,
+                            1.6,
+                        );
                     }
+                    None => camera.reset(),
                 }
+                camera.update();
+                camera.jolt(Vector2::new(0.0, 0.0), 1.0);
 
+                let room = Room {
+                    walls: &walls,
+                    carpet: &carpet,
+                    props: &props,
+                    humans: &humans,
+                };
+                let highlight = cross
+                    .current_speaker()
+                    .map(|(person_type, x, y)| person_sprite(person_type, x, y).2);
                 {
-                    // defense lawyer
-                    draw_person(&mut d, &humans, PersonType::Person1, 448.0, 210.0);
-                    // steve
-                    draw_person(&mut d, &humans, PersonType::Person4, 496.0, 210.0);
-                    // king
-                    draw_person(&mut d, &humans, PersonType::Person5, 400.0, 210.0);
-                    // petrocelli
-                    draw_person(&mut d, &humans, PersonType::Person12, 128.0, 210.0);
+                    let mut d = d.begin_mode2D(camera.camera2d());
+                    draw_courtroom(&mut d, room, &jury, &audience_pos, &[], 0, highlight);
+                    draw_person(
+                        &mut d,
+                        &humans,
+                        BOBO_EVANS.witness_type,
+                        BOBO_EVANS.x,
+                        BOBO_EVANS.y,
+                    );
                 }
+                draw_jury_sentiment(
+                    &mut d,
+                    settings.sentiment,
+                    &jury,
+                    &camera,
+                    &COURTROOM.resolve(&variables),
+                    i32::MAX,
+                    &variables,
+                );
+                cross.draw(&mut d, &humans, &glossary, &mut variables, paused);
 
-                if loop_counter < 100 {
-                    d.draw_rectangle(0, 0, 624, 624, Color::new(0, 0, 0, sat_counter));
-                    sat_counter = sat_counter.saturating_sub(4);
-                } else if loop_counter > 4300 {
-                    d.draw_rectangle(0, 0, 624, 624, Color::new(0, 0, 0, sat_counter));
-                    sat_counter = sat_counter.saturating_add(4);
+                if cross.is_done() && !scenes.is_transitioning() {
+                    variables.insert(completed_variable(3), 1);
+                    journal.open_at(3, &variables);
+                    scenes.switch_to(
+                        if settings.quiz { 11 } else { 4 },
+                        Transition::new(
+                            TransitionKind::Fade(Color::BLACK),
+                            120,
+                            ease::cubic_in_out,
+                        ),
+                    );
                 }
+            }
 
-                if loop_counter > 150 && loop_counter < 1550 {
-                    d.draw_rectangle_rounded(
-                        Rectangle::new(10.0, 305.0, 604.0, 290.0),
-                        0.1,
-                        10,
-                        Color::WHITE,
-                    );
-                    if loop_counter % 3 == 0 {
-                        x += 1;
-                    }
-                    let text = "Steve looks at the jury and sees one juror staring at him.\n\
-                    He looks back at them and makes eye contact.\n\
-                    He notices the juror has a somewhat disgusted facial expression.\n\
-                    As he continues looking over, he wonders why.\n\
-                    He flashes back to the start of the trial and what O'Brien said:\n\
-                    \"You're young, you're black, and you're on trial: what else do they need to know?\"";
-                    d.draw_text_rec(
-                        d.get_font_default(),
-                        match text.get(0..x) {
-                            Some(t) => t,
-                            None => text,
-                        },
-                        Rectangle::new(15.0, 310.0, 594.0, 280.0),
-                        20.0,
-                        1.0,
-                        true,
-                        Color::BLACK,
-                    )
-                } else if loop_counter == 1599 || loop_counter == 2801 {
-                    x = 0;
-                } else if loop_counter > 1600 && loop_counter < 2800 {
-                    d.draw_rectangle_rounded(
-                        Rectangle::new(10.0, 305.0, 604.0, 290.0),
-                        0.1,
-                        10,
-                        Color::WHITE,
-                    );
-                    if loop_counter % 3 == 0 {
-                        x += 1;
-                    }
-                    let text = "To try and break the ice, he waves.\n\
-                    The juror snarls and looks away.\n\
-                    With a heavy heart, he looks up at the American flag over the judge and places \
-                    his trust in the 12 members of the jury who are the ones to decide his fate.\n\
-                    He knows he is innocent and hopes the justice system sees him the same way.";
-                    d.draw_text_rec(
-                        d.get_font_default(),
-                        match text.get(0..x) {
-                            Some(t) => t,
-                            None => text,
-                        },
-                        Rectangle::new(15.0, 310.0, 594.0, 280.0),
-                        20.0,
-                        1.0,
-                        true,
-                        Color::BLACK,
-                    )
-                } else if loop_counter > 2900 && loop_counter < 4200 {
-                    d.draw_rectangle_rounded(
-                        Rectangle::new(10.0, 305.0, 604.0, 290.0),
-                        0.1,
-                        10,
-                        Color::WHITE,
+            4 => {
+                let mood = mood(&COURTROOM.resolve(&variables), i32::MAX, &variables);
+                if deliberation.draw(&mut d, &humans, &jury, &mut variables, paused, mood)
+                    && !scenes.is_transitioning()
+                {
+                    variables.insert(completed_variable(4), 1);
+                    journal.open_at(4, &variables);
+                    scenes.switch_to(
+                        if settings.quiz { 12 } else { u16::MAX },
+                        Transition::new(TransitionKind::Crossfade, 60, ease::quad_in_out),
                     );
-                    if loop_counter % 3 == 0 {
-                        x += 1;
-                    }
-                    let text = "But as he thinks of all this, he worries more and more about \
-                    the mainly white jury. He flashes back to O'Brien's statement again, and just can't \
-                    get it out of his mind. It's hard not to be worried or stressed under this situation.\n\
-                    Steve is handling it remarkably well.";
-                    d.draw_text_rec(
-                        d.get_font_default(),
-                        match text.get(0..x) {
-                            Some(t) => t,
-                            None => text,
-                        },
-                        Rectangle::new(15.0, 310.0, 594.0, 280.0),
-                        20.0,
-                        1.0,
-                        true,
-                        Color::BLACK,
-                    )
                 }
+            }
 
-                loop_counter += 1;
-                if loop_counter > 4400 {
-                    loop_counter = 0;
-                    game_state += 1;
+            10 | 11 => {
+                let current = scenes.current();
+                let quiz = quizzes[(current - 10) as usize].as_ref();
+                if quiz_scene.draw(&mut d, quiz, &mut variables, &mut notes, paused)
+                    && !scenes.is_transitioning()
+                {
+                    quiz_scene = QuizScene::new();
+                    scenes.switch_to(
+                        if current == 10 { 3 } else { 4 },
+                        Transition::new(TransitionKind::Crossfade, 40, ease::quad_in_out),
+                    );
                 }
             }
 
-            #[cfg(not(debug_assertions))]
-            3 => {
-                game_state = u16::MAX;
+            12 => {
+                draw_summary(&mut d, &quizzes, &variables);
+                if !paused && d.is_key_pressed(KeyboardKey::KEY_ENTER) && !scenes.is_transitioning()
+                {
+                    scenes.switch_to(
+                        u16::MAX,
+                        Transition::new(TransitionKind::Crossfade, 60, ease::quad_in_out),
+                    );
+                }
             }
 
             u16::MAX => {
@@ -515,6 +733,15 @@ fn main() {
                     375,
                     Some("https://github.com/tazz4843/monster"),
                 );
+                draw_text(
+                    &mut d,
+                    "Press F6 to save a report for your teacher",
+                    20,
+                    312,
+                    450,
+                    None,
+                );
+                draw_text(&mut d, "Press J to read your journal", 20, 312, 480, None);
                 draw_text(&mut d, "Press ESC to exit", 20, 312, 600, None);
             }
 
@@ -526,6 +753,334 @@ fn main() {
             #[cfg(not(debug_assertions))]
             _ => unsafe { unreachable_unchecked() },
         }
+        // while the journal is open letters are being written down, and ENTER closes it
+        if !journal.is_open() {
+            if backlog.is_open() {
+                if d.is_key_pressed(KeyboardKey::KEY_L) {
+                    backlog.close();
+                }
+            } else if inventory.is_open() {
+                if d.is_key_pressed(KeyboardKey::KEY_I) {
+                    inventory.toggle();
+                }
+            } else if glossary_menu.is_open() {
+                if d.is_key_pressed(KeyboardKey::KEY_G) {
+                    glossary_menu.toggle();
+                }
+            } else if (2..=4).contains(&scenes.current())
+                && !slot_menu.is_open()
+                && d.is_key_pressed(KeyboardKey::KEY_I)
+            {
+                inventory.toggle();
+            } else if (2..=4).contains(&scenes.current())
+                && !slot_menu.is_open()
+                && d.is_key_pressed(KeyboardKey::KEY_G)
+            {
+                glossary_menu.toggle();
+            } else if matches!(scenes.current(), 2..=4 | 12 | u16::MAX)
+                && !slot_menu.is_open()
+                && d.is_key_pressed(KeyboardKey::KEY_J)
+            {
+                journal.toggle();
+            } else if scenes.current() == 2
+                && !slot_menu.is_open()
+                && d.is_key_pressed(KeyboardKey::KEY_L)
+            {
+                backlog.open();
+            } else if scenes.current() != 0
+                && !scenes.is_transitioning()
+                && d.is_key_pressed(KeyboardKey::KEY_F5)
+            {
+                slot_menu.toggle(SlotMode::Save);
+            } else if scenes.current() != 0 && d.is_key_pressed(KeyboardKey::KEY_F6) {
+                let progress = Progress::new(
+                    scenes.current(),
+                    loop_counter,
+                    seed,
+                    variables.clone(),
+                    notes.clone(),
+                );
+                let notice = match Report::new(&progress, &quizzes).export() {
+                    Ok(path) => format!("Report saved to {}", path.display()),
+                    Err(e) => format!("Couldn't save the report: {}", e),
+                };
+                report_notice = Some((notice, 240));
+            } else if !scenes.is_transitioning() && d.is_key_pressed(KeyboardKey::KEY_F9) {
+                slot_menu.toggle(SlotMode::Load);
+            }
+        }
+        backlog.draw(&mut d);
+        inventory.draw(&mut d, &variables);
+        glossary_menu.draw(&mut d, &glossary);
+        journal.draw(&mut d, &variables, &mut notes);
+        if slot_menu.is_open() {
+            let current = Progress::new(
+                scenes.current(),
+                loop_counter,
+                seed,
+                variables.clone(),
+                notes.clone(),
+            );
+            resume = slot_menu.draw(&mut d, &current).or(resume);
+        }
+        if let Some((notice, frames)) = report_notice.as_mut() {
+            d.draw_rectangle(0, 0, 624, 30, Color::WHITE.fade(0.85));
+            draw_text(&mut d, notice, 16, 312, 15, None);
+            *frames -= 1;
+            if *frames <= 0 {
+                report_notice = None;
+            }
+        }
+
+        if let Some(progress) = resume.take() {
+            seed = progress.seed;
+            let (j, a) = seat_people(seed);
+            jury = j;
+            audience_pos = a;
+            loop_counter = progress.frame;
+            variables = progress.variables;
+            notes = progress.notes;
+            cross = CrossExamination::new(&BOBO_EVANS);
+            deliberation = Deliberation::new();
+            quiz_scene = QuizScene::new();
+
+            let script = COURTROOM.resolve(&variables);
+            history = LineHistory::up_to(&script, loop_counter);
+            restore_tracks(&mut audio, &thread, &script, loop_counter);
+
+            scenes.switch_to(
+                progress.scene,
+                Transition::new(TransitionKind::Fade(Color::BLACK), 120, ease::cubic_in_out),
+            );
+        }
+
+        scenes.draw(&mut d);
         d.draw_text(d.get_fps().to_string().as_str(), 0, 0, 8, Color::WHITE);
     }
+
+    // the title screen has nothing worth saving, and saving there would overwrite real progress
+    if scenes.current() != 0 {
+        let progress = Progress::new(scenes.current(), loop_counter, seed, variables, notes);
+        if let Err(e) = progress.save(&autosave_path()) {
+            trace_log(
+                TraceLogType::LOG_WARNING,
+                format!("failed to save progress: {}", e).as_str(),
+            );
+        }
+    }
+}
+
+/// Brings back whatever music or ambience the script had playing at `frame`. Tracks that are
+/// already on keep playing.
+fn restore_tracks(audio: &mut AudioManager, thread: &RaylibThread, script: &[Beat], frame: i32) {
+    let earlier: Vec<&Beat> = script.iter().filter(|b| b.start < frame).collect();
+
+    for channel in [Channel::Music, Channel::Ambience].iter() {
+        let track = earlier.iter().fold(None, |track, beat| match beat.action {
+            Action::PlayTrack(c, path) if c == *channel => Some(path),
+            Action::StopTrack(c) if c == *channel => None,
+            _ => track,
+        });
+        match track {
+            Some(path) => audio.play_track(thread, *channel, path, 1),
+            None => audio.stop_track(*channel, 1),
+        }
+    }
+}
+
+/// The textures the courtroom is drawn with.
+#[derive(Copy, Clone)]
+struct Room<'a> {
+    walls: &'a Texture2D,
+    carpet: &'a Texture2D,
+    props: &'a Texture2D,
+    humans: &'a HashMap<PersonType, Texture2D>,
+}
+
+/// Draws the courtroom and everyone in it in world coordinates, moving people through the
+/// script's gestures. `highlight` is the sprite of whoever is speaking.
+fn draw_courtroom<D: RaylibDraw>(
+    d: &mut D,
+    room: Room,
+    jury: &Jury,
+    audience: &[(f32, f32, PersonType)],
+    script: &[Beat],
+    frame: i32,
+    highlight: Option<Rectangle>,
+) {
+    let Room {
+        walls,
+        carpet,
+        props,
+        humans,
+    } = room;
+
+    // draw walls
+    {
+        // Draw all the corner walls
+        draw_wall(d, walls, WallType::TallUpperLeftCornerWall, 0, 0);
+        draw_wall(d, walls, WallType::TallUpperRightCornerWall, 12, 0);
+        draw_wall(d, walls, WallType::TallLowerLeftCornerWall, 0, 12);
+        draw_wall(d, walls, WallType::TallLowerRightCornerWall, 12, 12);
+
+        // Draw the vertical walls
+        for i in 1..12 {
+            draw_wall(d, walls, WallType::TallTopBottomWall, i, 0);
+            if i < 5 || i > 7 {
+                draw_wall(d, walls, WallType::TallTopBottomWall, i, 12);
+            }
+        }
+        for i in 1..12 {
+            draw_wall(d, walls, WallType::TallVerticalWall, 0, i);
+            draw_wall(d, walls, WallType::TallVerticalWall, 12, i);
+        }
+    }
+    // draw carpets
+    {
+        for x in 1..=11 {
+            for y in 1..=11 {
+                let carpet_type = match (x, y) {
+                    (1, 1) => CarpetType::LeftTopWall,
+                    (1, 11) => CarpetType::LeftBottomWall,
+                    (11, 1) => CarpetType::RightTopWall,
+                    (11, 11) => CarpetType::RightBottomWall,
+                    (5, 11) => CarpetType::BottomLeftCornerWall,
+                    (6, 11) => CarpetType::NoWalls,
+                    (7, 11) => CarpetType::BottomRightCornerWall,
+                    (x, _) if x == 1 => CarpetType::LeftWall,
+                    (x, _) if x == 11 => CarpetType::RightWall,
+                    (_, y) if y == 1 => CarpetType::TopWall,
+                    (_, y) if y == 11 => CarpetType::BottomWall,
+                    _ => CarpetType::NoWalls,
+                };
+                draw_carpet(d, carpet, carpet_type, x, y);
+            }
+        }
+        draw_carpet(d, carpet, CarpetType::LeftWall, 5, 12);
+        draw_carpet(d, carpet, CarpetType::NoWalls, 6, 12);
+        draw_carpet(d, carpet, CarpetType::RightWall, 7, 12);
+    }
+    // draw stand
+    {
+        draw_prop(d, props, PropTypes::LargeBlueChair, 6, 1);
+        draw_prop(d, props, PropTypes::LargeBlueChair, 9, 1);
+        draw_wall(d, walls, WallType::ShortRightWall, 4, 2);
+        for i in 5..10 {
+            draw_wall(
+                d,
+                walls,
+                match i {
+                    5 => WallType::TallLeftWall,
+                    6 => WallType::TallWall,
+                    7 => WallType::TallRightWall,
+                    _ => WallType::ShortWall,
+                },
+                i,
+                2,
+            );
+        }
+        draw_wall(d, walls, WallType::TallLeftRightWall, 9, 2);
+        draw_wall(d, walls, WallType::ShortLeftWall, 10, 2);
+
+        for i in 1..=11 {
+            if i < 4 || i > 8 {
+                draw_wall(d, walls, WallType::ShortWall, i, 5)
+            }
+        }
+        draw_wall(d, walls, WallType::ShortLeftWall, 4, 5);
+        draw_wall(d, walls, WallType::ShortRightWall, 8, 5);
+    }
+    // draw jury area
+    for x in 1..=3 {
+        for y in 1..=2 {
+            draw_wall(d, walls, WallType::Floor, x, y)
+        }
+    }
+
+    if let Some(sprite) = highlight {
+        draw_highlight(d, sprite);
+    }
+
+    // draw jury
+    for juror in jury.iter() {
+        let (dx, dy, flip) = gesture_motion(script, frame, Actor::Juror(juror.id));
+        draw_person_ex(
+            d,
+            humans,
+            juror.person_type,
+            juror.x + dx,
+            juror.y + dy,
+            flip,
+        )
+    }
+
+    for (x, y, person_type) in audience.iter() {
+        draw_person(d, humans, *person_type, *x, *y)
+    }
+
+    // draw defense/prosecution tables
+    for i in 1..=11 {
+        if i < 5 || i > 7 {
+            draw_prop(d, props, PropTypes::LargeTable, i, 4)
+        }
+    }
+
+    // draw the judge, lawyers and defendants
+    for member in CastMember::ALL.iter() {
+        let entry = member.entry();
+        let (dx, dy, flip) = gesture_motion(script, frame, Actor::Cast(*member));
+        draw_person_ex(
+            d,
+            humans,
+            entry.person_type,
+            entry.x + dx,
+            entry.y + dy,
+            flip,
+        );
+    }
+}
+
+/// Shows how the jury feels about Steve, if the reader asked to see it.
+fn draw_jury_sentiment(
+    d: &mut RaylibDrawHandle,
+    display: SentimentDisplay,
+    jury: &Jury,
+    camera: &Camera,
+    script: &[Beat],
+    frame: i32,
+    variables: &BTreeMap<String, i32>,
+) {
+    match display {
+        SentimentDisplay::Hidden => {}
+        SentimentDisplay::Meter => draw_mood_meter(d, mood(script, frame, variables)),
+        SentimentDisplay::Expressions => {
+            for juror in jury.iter() {
+                let emote = match sentiment(script, frame, variables, juror.id) {
+                    s if s >= 2 => Emote::Heart,
+                    s if s <= -2 => Emote::Anger,
+                    _ => continue,
+                };
+                let (_, _, sprite) = person_sprite(juror.person_type, juror.x, juror.y);
+                // long past the pop-in, so the marks stay put
+                draw_emote(d, emote, camera.world_to_screen(sprite), 60);
+            }
+        }
+    }
+}
+
+/// Seats the jury and audience. The same seed always gives the same courtroom.
+fn seat_people(seed: u64) -> (Jury, Vec<(f32, f32, PersonType)>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let jury = Jury::new(&mut rng);
+
+    let mut audience_pos: Vec<(f32, f32, PersonType)> = Vec::new();
+    for _ in 1..=100 {
+        audience_pos.push((
+            rng.gen_range(48..=550) as f32,
+            rng.gen_range(260..=540) as f32,
+            rand_person(&mut rng, true),
+        ));
+    }
+
+    (jury, audience_pos)
 }
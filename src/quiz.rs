@@ -0,0 +1,473 @@
+use crate::drawing::{draw_choice_menu, draw_text, type_into, wrap_text};
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::logging::trace_log;
+use raylib::core::text::measure_text;
+use raylib::ffi::{KeyboardKey, TraceLogType};
+use std::collections::BTreeMap;
+use std::fs;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum QuestionKind {
+    /// Pick one of several options.
+    Choice {
+        options: Vec<String>,
+        correct: usize,
+    },
+    /// Type an answer, which counts if it contains any of the accepted words or phrases and none
+    /// of the rejected ones. See [`matches`].
+    Text {
+        /// Shown to a reader who got it wrong.
+        answer: String,
+        accepted: Vec<String>,
+        rejected: Vec<String>,
+    },
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Question {
+    pub prompt: String,
+    pub kind: QuestionKind,
+}
+
+impl Question {
+    /// Checks a typed answer, ignoring case and surrounding spaces.
+    pub fn accepts(&self, answer: &str) -> bool {
+        match &self.kind {
+            QuestionKind::Choice { options, correct } => options
+                .get(*correct)
+                .is_some_and(|o| o.eq_ignore_ascii_case(answer.trim())),
+            QuestionKind::Text {
+                accepted, rejected, ..
+            } => {
+                let words = words(answer);
+                accepted.iter().any(|a| matches(a, &words))
+                    && !rejected.iter().any(|r| matches(r, &words))
+            }
+        }
+    }
+
+    /// The answer to show a reader who got it wrong.
+    pub fn expected(&self) -> &str {
+        match &self.kind {
+            QuestionKind::Choice { options, correct } => &options[*correct],
+            QuestionKind::Text { answer, .. } => answer,
+        }
+    }
+}
+
+/// Splits typed text into lowercase words.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Whether `words` contain the words of `pattern` in a row. A word in the pattern ending in `*`
+/// matches any word starting with it, so `worr*` matches "worried" and "worry".
+fn matches(pattern: &str, words: &[String]) -> bool {
+    let pattern: Vec<String> = pattern
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    let word_matches = |p: &String, word: &String| match p.strip_suffix('*') {
+        Some(prefix) => word.starts_with(prefix),
+        None => p == word,
+    };
+    !pattern.is_empty()
+        && words
+            .windows(pattern.len())
+            .any(|window| pattern.iter().zip(window).all(|(p, w)| word_matches(p, w)))
+}
+
+/// A reading-comprehension quiz, read from `assets/quizzes/<id>.quiz`.
+///
+/// Quiz files are made of lines starting with a keyword:
+///
+/// ```text
+/// # comments are skipped
+/// title Chapter 1: The Courtroom
+/// choice Who stared at Steve from the jury box?
+/// option The judge
+/// answer A juror
+/// text What did Steve do to break the ice?
+/// answer He waved at the juror
+/// accept wav*
+/// reject not
+/// ```
+///
+/// `choice` and `text` start a question. A choice question lists its options with `option`,
+/// using `answer` for the correct one. A text question gives the `answer` to show a reader who
+/// got it wrong, and lists the words or phrases it `accept`s and those it `reject`s.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Quiz {
+    pub id: String,
+    pub title: String,
+    pub questions: Vec<Question>,
+}
+
+impl Quiz {
+    pub fn parse(id: &str, text: &str) -> Result<Quiz, String> {
+        let mut title = id.to_string();
+        let mut questions: Vec<Question> = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim().to_string();
+            let error = |message: &str| format!("{}.quiz line {}: {}", id, number + 1, message);
+
+            match (key, questions.last_mut()) {
+                ("title", _) => title = value,
+                ("choice", _) => questions.push(Question {
+                    prompt: value,
+                    kind: QuestionKind::Choice {
+                        options: Vec::new(),
+                        correct: usize::MAX,
+                    },
+                }),
+                ("text", _) => questions.push(Question {
+                    prompt: value,
+                    kind: QuestionKind::Text {
+                        answer: String::new(),
+                        accepted: Vec::new(),
+                        rejected: Vec::new(),
+                    },
+                }),
+                ("option", Some(question)) | ("answer", Some(question)) => {
+                    match &mut question.kind {
+                        QuestionKind::Choice { options, correct } => {
+                            if key == "answer" {
+                                *correct = options.len();
+                            }
+                            options.push(value);
+                        }
+                        QuestionKind::Text { answer, .. } if key == "answer" => *answer = value,
+                        _ => return Err(error("options only go with choice questions")),
+                    }
+                }
+                ("accept", Some(question)) | ("reject", Some(question)) => {
+                    match &mut question.kind {
+                        QuestionKind::Text {
+                            accepted, rejected, ..
+                        } => {
+                            if key == "accept" {
+                                accepted.push(value)
+                            } else {
+                                rejected.push(value)
+                            }
+                        }
+                        _ => return Err(error("accepted answers only go with text questions")),
+                    }
+                }
+                _ => return Err(error(&format!("unexpected `{}`", key))),
+            }
+        }
+
+        for question in questions.iter() {
+            let complete = match &question.kind {
+                QuestionKind::Choice { options, correct } => *correct < options.len(),
+                QuestionKind::Text {
+                    answer, accepted, ..
+                } => !answer.is_empty() && !accepted.is_empty(),
+            };
+            if !complete {
+                return Err(format!(
+                    "{}.quiz: \"{}\" has no answer",
+                    id, question.prompt
+                ));
+            }
+        }
+
+        Ok(Quiz {
+            id: id.to_string(),
+            title,
+            questions,
+        })
+    }
+
+    /// Loads a quiz from the assets, logging why if it can't.
+    pub fn load(id: &str) -> Option<Quiz> {
+        let path = format!("assets/quizzes/{}.quiz", id);
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {}", path, e))
+            .and_then(|text| Quiz::parse(id, &text));
+        match parsed {
+            Ok(quiz) => Some(quiz),
+            Err(e) => {
+                trace_log(TraceLogType::LOG_WARNING, &e);
+                None
+            }
+        }
+    }
+
+    /// Story variable recording whether a question was answered right (1) or wrong (2).
+    pub fn result_variable(&self, question: usize) -> String {
+        format!("quiz.{}.{}", self.id, question)
+    }
+
+    /// Questions answered right, and questions answered at all.
+    pub fn score(&self, variables: &BTreeMap<String, i32>) -> (usize, usize) {
+        let results: Vec<i32> = (0..self.questions.len())
+            .filter_map(|i| variables.get(&self.result_variable(i)).copied())
+            .collect();
+        (results.iter().filter(|r| **r == 1).count(), results.len())
+    }
+}
+
+/// Takes the reader through a quiz one question at a time.
+pub struct QuizScene {
+    question: usize,
+    selected: usize,
+    typed: String,
+    /// Whether the question on screen was answered right, once it has been answered.
+    feedback: Option<bool>,
+}
+
+impl QuizScene {
+    pub fn new() -> QuizScene {
+        QuizScene {
+            question: 0,
+            selected: 0,
+            typed: String::new(),
+            feedback: None,
+        }
+    }
+
+    /// Handles input and draws the question on screen. Each answer is kept in `answers` under
+    /// the question's result variable. Returns true once the reader is through the quiz, or
+    /// straight away if there is none.
+    pub fn draw(
+        &mut self,
+        d: &mut RaylibDrawHandle,
+        quiz: Option<&Quiz>,
+        variables: &mut BTreeMap<String, i32>,
+        answers: &mut BTreeMap<String, String>,
+        paused: bool,
+    ) -> bool {
+        let quiz = match quiz {
+            Some(quiz) => quiz,
+            None => return true,
+        };
+        // a quiz continued from a save starts at the first question it has no answer for
+        if self.feedback.is_none() {
+            while self.question < quiz.questions.len()
+                && variables.contains_key(&quiz.result_variable(self.question))
+            {
+                self.question += 1;
+            }
+        }
+        let question = match quiz.questions.get(self.question) {
+            Some(question) => question,
+            None => return true,
+        };
+
+        let enter = !paused && d.is_key_pressed(KeyboardKey::KEY_ENTER);
+        if let Some(correct) = self.feedback {
+            if enter {
+                *self = QuizScene {
+                    question: self.question + 1,
+                    ..QuizScene::new()
+                };
+            }
+            let (text, color) = if correct {
+                ("Correct!".to_string(), Color::new(60, 160, 80, 255))
+            } else {
+                (
+                    format!("Not quite. The answer was: {}", question.expected()),
+                    Color::new(200, 50, 50, 255),
+                )
+            };
+            let x = 312 - measure_text(&text, 20) / 2;
+            d.draw_text(&text, x.max(20), 470, 20, color);
+        } else if !paused {
+            let answer = match &question.kind {
+                QuestionKind::Choice { options, .. } => {
+                    if d.is_key_pressed(KeyboardKey::KEY_UP) {
+                        self.selected = (self.selected + options.len() - 1) % options.len();
+                    } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+                        self.selected = (self.selected + 1) % options.len();
+                    }
+                    options[self.selected].clone()
+                }
+                QuestionKind::Text { .. } => {
+                    type_into(d, &mut self.typed, 60);
+                    self.typed.clone()
+                }
+            };
+            if enter && !answer.trim().is_empty() {
+                let correct = question.accepts(&answer);
+                let result = if correct { 1 } else { 2 };
+                variables.insert(quiz.result_variable(self.question), result);
+                answers.insert(
+                    quiz.result_variable(self.question),
+                    answer.trim().to_string(),
+                );
+                self.feedback = Some(correct);
+            }
+        }
+
+        draw_text(d, &quiz.title, 28, 312, 40, None);
+        draw_text(
+            d,
+            &format!("Question {} of {}", self.question + 1, quiz.questions.len()),
+            20,
+            312,
+            80,
+            None,
+        );
+        for (i, line) in wrap_text(&question.prompt, 20, 540).iter().enumerate() {
+            d.draw_text(line, 42, 110 + i as i32 * 24, 20, Color::BLACK);
+        }
+
+        match &question.kind {
+            QuestionKind::Choice { options, .. } => {
+                let options: Vec<(&str, i32)> = options.iter().map(|o| (o.as_str(), 0)).collect();
+                draw_choice_menu(d, &options, self.selected);
+            }
+            QuestionKind::Text { .. } => {
+                d.draw_rectangle_lines(42, 200, 540, 40, Color::BLACK);
+                d.draw_text(&self.typed, 52, 210, 20, Color::BLACK);
+                if self.feedback.is_none() {
+                    let caret = 52 + measure_text(&self.typed, 20);
+                    d.draw_text("_", caret, 210, 20, Color::DARKGRAY);
+                }
+            }
+        }
+
+        let hint = match (self.feedback, &question.kind) {
+            (Some(_), _) => "Press ENTER to continue...",
+            (None, QuestionKind::Choice { .. }) => "UP/DOWN to pick, ENTER to answer",
+            (None, QuestionKind::Text { .. }) => "Type your answer, ENTER to answer",
+        };
+        draw_text(d, hint, 20, 312, 600, None);
+        false
+    }
+}
+
+/// Draws every quiz's score and the total. Quizzes that failed to load are left out.
+pub fn draw_summary(
+    d: &mut RaylibDrawHandle,
+    quizzes: &[Option<Quiz>],
+    variables: &BTreeMap<String, i32>,
+) {
+    draw_text(d, "Quiz Results", 32, 312, 60, None);
+    let (mut total_right, mut total_questions) = (0, 0);
+    for (i, quiz) in quizzes.iter().flatten().enumerate() {
+        let (right, _) = quiz.score(variables);
+        total_right += right;
+        total_questions += quiz.questions.len();
+        let y = 130 + i as i32 * 36;
+        d.draw_text(&quiz.title, 62, y, 20, Color::BLACK);
+        let score = format!("{} / {}", right, quiz.questions.len());
+        let x = 562 - measure_text(&score, 20);
+        d.draw_text(&score, x, y, 20, Color::BLACK);
+    }
+    match (total_right * 100).checked_div(total_questions) {
+        Some(percent) => draw_text(
+            d,
+            &format!(
+                "Total: {} / {} ({}%)",
+                total_right, total_questions, percent
+            ),
+            24,
+            312,
+            420,
+            None,
+        ),
+        None => draw_text(d, "There were no quizzes this time.", 20, 312, 200, None),
+    }
+    draw_text(d, "Press ENTER to continue...", 24, 312, 600, None);
+}
+
+impl Default for QuizScene {
+    fn default() -> QuizScene {
+        QuizScene::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "
+        # a comment
+        title Chapter 1
+        choice Who stared at Steve?
+        option The judge
+        answer A juror
+        text What did Steve do?
+        answer He waved at the juror
+        accept wav*
+        reject not
+        reject didn't
+    ";
+
+    #[test]
+    fn parses_both_kinds_of_question() {
+        let quiz = Quiz::parse("sample", SAMPLE).unwrap();
+        assert_eq!(quiz.title, "Chapter 1");
+        assert_eq!(quiz.questions.len(), 2);
+        assert_eq!(
+            quiz.questions[0].kind,
+            QuestionKind::Choice {
+                options: vec!["The judge".to_string(), "A juror".to_string()],
+                correct: 1,
+            }
+        );
+        assert_eq!(quiz.questions[1].prompt, "What did Steve do?");
+    }
+
+    #[test]
+    fn text_answers_are_matched_loosely() {
+        let quiz = Quiz::parse("sample", SAMPLE).unwrap();
+        assert!(quiz.questions[1].accepts("  He WAVED at them "));
+        assert!(!quiz.questions[1].accepts("he looked away"));
+        assert!(quiz.questions[0].accepts("a juror"));
+        assert!(!quiz.questions[0].accepts("The judge"));
+        assert_eq!(quiz.questions[1].expected(), "He waved at the juror");
+    }
+
+    #[test]
+    fn text_answers_match_whole_words_and_honor_rejections() {
+        let quiz = Quiz::parse("sample", SAMPLE).unwrap();
+        let question = &quiz.questions[1];
+        assert!(!question.accepts("he did not wave"));
+        assert!(!question.accepts("He didn't wave at all"));
+        // the accepted word has to start a word, not just appear inside one
+        assert!(!question.accepts("a microwave"));
+
+        let phrase = Quiz::parse("sample", "text What?\nanswer A deal\naccept got a deal").unwrap();
+        assert!(phrase.questions[0].accepts("He got a deal."));
+        assert!(!phrase.questions[0].accepts("a deal he got"));
+    }
+
+    #[test]
+    fn rejects_questions_without_an_answer() {
+        assert!(Quiz::parse("bad", "choice Who?\noption Nobody").is_err());
+        assert!(Quiz::parse("bad", "option Stray").is_err());
+        assert!(Quiz::parse("bad", "text Why?\noption Because").is_err());
+        assert!(Quiz::parse("bad", "text Why?\naccept because").is_err());
+    }
+
+    #[test]
+    fn shipped_quizzes_parse() {
+        for id in ["courtroom", "cross_examination"].iter() {
+            let text = fs::read_to_string(format!("assets/quizzes/{}.quiz", id)).unwrap();
+            assert!(Quiz::parse(id, &text).is_ok(), "{}", id);
+        }
+    }
+
+    #[test]
+    fn scores_recorded_answers() {
+        let quiz = Quiz::parse("sample", SAMPLE).unwrap();
+        let mut variables = BTreeMap::new();
+        variables.insert(quiz.result_variable(0), 1);
+        variables.insert(quiz.result_variable(1), 2);
+        assert_eq!(quiz.score(&variables), (1, 2));
+    }
+}
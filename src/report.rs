@@ -0,0 +1,314 @@
+use crate::journal::PROMPTS;
+use crate::quiz::Quiz;
+use crate::save::{data_dir, format_timestamp, Progress};
+use crate::scene::scene_title;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Note holding the name the reader entered at the start.
+pub const STUDENT: &str = "student";
+
+/// The chapters of the book, which count as completed once the reader moves past them.
+pub const CHAPTERS: [u16; 3] = [2, 3, 4];
+
+/// Every scene time is kept for, in reading order. The quiz results and the end screen are left
+/// out, as the reader can stay on them for as long as they like.
+const SCENES: [u16; 7] = [5, 1, 2, 10, 3, 11, 4];
+
+/// Frames drawn per second, for turning the time kept on scenes into seconds.
+const FPS: i32 = 60;
+
+/// Story variable counting the frames spent on a scene.
+pub fn time_variable(scene: u16) -> String {
+    format!("time.{}", scene)
+}
+
+/// Story variable set once the reader finishes a chapter.
+pub fn completed_variable(scene: u16) -> String {
+    format!("completed.{}", scene)
+}
+
+/// Counts a frame spent on `scene` into the story variables, so the time is saved with
+/// progress. Call once per frame the reader is on it. Scenes the report leaves out aren't
+/// counted.
+pub fn count_frame(scene: u16, variables: &mut BTreeMap<String, i32>) {
+    if !SCENES.contains(&scene) {
+        return;
+    }
+    *variables.entry(time_variable(scene)).or_insert(0) += 1;
+}
+
+/// One quiz question and how the reader answered it.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Answer {
+    pub quiz: String,
+    pub question: String,
+    pub answer: String,
+    pub correct: bool,
+}
+
+/// What a teacher gets to see of a reader's progress through the book.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Report {
+    pub student: String,
+    /// Seconds since the Unix epoch.
+    pub generated_at: u64,
+    /// Each chapter's title and whether it was completed.
+    pub chapters: Vec<(&'static str, bool)>,
+    /// Each scene's title and the seconds spent on it, leaving out scenes never seen.
+    pub time: Vec<(&'static str, i32)>,
+    pub answers: Vec<Answer>,
+    /// Each journal prompt the reader wrote something for, and what they wrote.
+    pub journal: Vec<(&'static str, String)>,
+    /// Questions answered right, out of every question in the quizzes.
+    pub score: (usize, usize),
+}
+
+impl Report {
+    pub fn new(progress: &Progress, quizzes: &[Option<Quiz>]) -> Report {
+        let variables = &progress.variables;
+        let chapters = CHAPTERS
+            .iter()
+            .map(|c| {
+                (
+                    scene_title(*c),
+                    variables.contains_key(&completed_variable(*c)),
+                )
+            })
+            .collect();
+        let time = SCENES
+            .iter()
+            .filter_map(|s| Some((scene_title(*s), variables.get(&time_variable(*s))? / FPS)))
+            .collect();
+
+        let mut answers = Vec::new();
+        let mut score = (0, 0);
+        for quiz in quizzes.iter().flatten() {
+            score.0 += quiz.score(variables).0;
+            score.1 += quiz.questions.len();
+            for (i, question) in quiz.questions.iter().enumerate() {
+                let name = quiz.result_variable(i);
+                if let Some(answer) = progress.notes.get(&name) {
+                    answers.push(Answer {
+                        quiz: quiz.title.clone(),
+                        question: question.prompt.clone(),
+                        answer: answer.clone(),
+                        correct: variables.get(&name) == Some(&1),
+                    });
+                }
+            }
+        }
+
+        let journal = PROMPTS
+            .iter()
+            .filter_map(|p| Some((p.text, progress.notes.get(&p.note())?.trim().to_string())))
+            .filter(|(_, entry)| !entry.is_empty())
+            .collect();
+
+        Report {
+            student: progress.notes.get(STUDENT).cloned().unwrap_or_default(),
+            generated_at: progress.saved_at,
+            chapters,
+            time,
+            answers,
+            journal,
+            score,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let chapters: Vec<String> = self
+            .chapters
+            .iter()
+            .map(|(title, done)| {
+                format!(
+                    "    {{ \"title\": {}, \"completed\": {} }}",
+                    json_string(title),
+                    done
+                )
+            })
+            .collect();
+        let time: Vec<String> = self
+            .time
+            .iter()
+            .map(|(title, secs)| {
+                format!(
+                    "    {{ \"scene\": {}, \"seconds\": {} }}",
+                    json_string(title),
+                    secs
+                )
+            })
+            .collect();
+        let answers: Vec<String> = self
+            .answers
+            .iter()
+            .map(|a| {
+                format!(
+                    "    {{ \"quiz\": {}, \"question\": {}, \"answer\": {}, \"correct\": {} }}",
+                    json_string(&a.quiz),
+                    json_string(&a.question),
+                    json_string(&a.answer),
+                    a.correct
+                )
+            })
+            .collect();
+        let journal: Vec<String> = self
+            .journal
+            .iter()
+            .map(|(prompt, entry)| {
+                format!(
+                    "    {{ \"prompt\": {}, \"entry\": {} }}",
+                    json_string(prompt),
+                    json_string(entry)
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\n  \"student\": {},\n  \"generated_at\": {},\n  \"chapters\": [\n{}\n  ],\n  \
+            \"time\": [\n{}\n  ],\n  \"answers\": [\n{}\n  ],\n  \"journal\": [\n{}\n  ],\n  \
+            \"score\": {{ \"right\": {}, \"questions\": {} }}\n}}\n",
+            json_string(&self.student),
+            json_string(&format_timestamp(self.generated_at)),
+            chapters.join(",\n"),
+            time.join(",\n"),
+            answers.join(",\n"),
+            journal.join(",\n"),
+            self.score.0,
+            self.score.1
+        )
+    }
+
+    /// One row per fact, all starting with the student's name so reports can be pasted together.
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec!["student,section,item,value,correct".to_string()];
+        let mut row = |section: &str, item: &str, value: &str, correct: &str| {
+            rows.push(
+                [self.student.as_str(), section, item, value, correct]
+                    .iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+        };
+        for (title, done) in self.chapters.iter() {
+            row(
+                "chapter",
+                title,
+                if *done { "completed" } else { "not completed" },
+                "",
+            );
+        }
+        for (title, secs) in self.time.iter() {
+            row("seconds", title, &secs.to_string(), "");
+        }
+        for a in self.answers.iter() {
+            let item = format!("{}: {}", a.quiz, a.question);
+            row(
+                "answer",
+                &item,
+                &a.answer,
+                if a.correct { "yes" } else { "no" },
+            );
+        }
+        for (prompt, entry) in self.journal.iter() {
+            row("journal", prompt, entry, "");
+        }
+        let score = format!("{}/{}", self.score.0, self.score.1);
+        row("score", "total", &score, "");
+        rows.join("\n") + "\n"
+    }
+
+    /// Writes the report as both JSON and CSV, returning the path of the JSON one.
+    pub fn export(&self) -> io::Result<PathBuf> {
+        let dir = data_dir().join("reports");
+        fs::create_dir_all(&dir)?;
+        let name: String = self
+            .student
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        let when = format_timestamp(self.generated_at).replace([' ', ':'], "-");
+        let path = dir.join(format!(
+            "{}-{}",
+            if name.is_empty() { "reader" } else { &name },
+            when
+        ));
+        fs::write(path.with_extension("csv"), self.to_csv())?;
+        let json = path.with_extension("json");
+        fs::write(&json, self.to_json())?;
+        Ok(json)
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("say \"hi\"\n"), "\"say \\\"hi\\\"\\n\"");
+    }
+
+    #[test]
+    fn quotes_csv_fields_only_when_needed() {
+        assert_eq!(csv_field("King"), "King");
+        assert_eq!(csv_field("Yes, \"sir\""), "\"Yes, \"\"sir\"\"\"");
+    }
+
+    #[test]
+    fn reports_time_and_completed_chapters() {
+        let mut variables = BTreeMap::new();
+        variables.insert(completed_variable(2), 1);
+        variables.insert(time_variable(2), 95 * FPS + 30);
+        let mut notes = BTreeMap::new();
+        notes.insert(STUDENT.to_string(), "Steve".to_string());
+        notes.insert(PROMPTS[0].note(), "  ".to_string());
+        let report = Report::new(&Progress::new(3, 0, 0, variables, notes), &[]);
+
+        assert_eq!(report.student, "Steve");
+        assert_eq!(report.chapters[0], ("The Courtroom", true));
+        assert_eq!(report.chapters[1], ("Cross-Examination", false));
+        assert_eq!(report.time, vec![("The Courtroom", 95)]);
+        assert!(report.journal.is_empty());
+        assert!(report
+            .to_csv()
+            .contains("Steve,seconds,The Courtroom,95,\n"));
+    }
+
+    #[test]
+    fn counts_frames_only_on_reported_scenes() {
+        let mut variables = BTreeMap::new();
+        for scene in [2, 2, 0, 12, u16::MAX].iter() {
+            count_frame(*scene, &mut variables);
+        }
+        assert_eq!(variables.len(), 1);
+        assert_eq!(variables.get(&time_variable(2)), Some(&2));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::scene::{scene_title, SCENES};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version written to new save files. Bump it when the format or the story changes in a way old
+/// saves need to be adjusted for, and handle the old version in [`Progress::migrate`].
+pub const SAVE_VERSION: u32 = 1;
+
+/// Where the reader is in the book, and everything needed to put them back there.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Progress {
+    pub scene: u16,
+    /// Frames into the scene's script.
+    pub frame: i32,
+    /// Seed the jury and audience were seated with.
+    pub seed: u64,
+    pub variables: BTreeMap<String, i32>,
+    /// Text the reader typed in, like their name and quiz answers.
+    pub notes: BTreeMap<String, String>,
+    /// Title of the scene, as shown in the save menu.
+    pub title: String,
+    /// Seconds since the Unix epoch, or 0 if unknown.
+    pub saved_at: u64,
+}
+
+impl Progress {
+    pub fn new(
+        scene: u16,
+        frame: i32,
+        seed: u64,
+        variables: BTreeMap<String, i32>,
+        notes: BTreeMap<String, String>,
+    ) -> Progress {
+        let saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Progress {
+            scene,
+            frame,
+            seed,
+            variables,
+            notes,
+            title: scene_title(scene).to_string(),
+            saved_at,
+        }
+    }
+
+    /// Reads a save file. Unknown lines are skipped, but saves written by a newer version, or
+    /// that are on a scene this build doesn't have, are rejected.
+    pub fn parse(text: &str) -> Option<Progress> {
+        let mut version = None;
+        let mut scene = None;
+        let mut frame = None;
+        let mut seed = None;
+        let mut variables = BTreeMap::new();
+        let mut notes = BTreeMap::new();
+        let mut title = None;
+        let mut saved_at = None;
+
+        for line in text.lines() {
+            let (key, value) = match line.split_once(' ') {
+                Some(pair) => pair,
+                None => continue,
+            };
+            match key {
+                "version" => version = value.parse().ok(),
+                "scene" => scene = value.parse().ok(),
+                "frame" => frame = value.parse().ok(),
+                "seed" => seed = value.parse().ok(),
+                "title" => title = Some(value.to_string()),
+                "saved_at" => saved_at = value.parse().ok(),
+                "var" => {
+                    if let Some((name, value)) = value.split_once(' ') {
+                        if let Ok(value) = value.parse() {
+                            variables.insert(name.to_string(), value);
+                        }
+                    }
+                }
+                "note" => {
+                    if let Some((name, text)) = value.split_once(' ') {
+                        notes.insert(name.to_string(), unescape(text));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let version = version?;
+        if version > SAVE_VERSION {
+            return None;
+        }
+        let mut progress = Progress {
+            scene: scene?,
+            frame: frame?,
+            seed: seed?,
+            variables,
+            notes,
+            title: title?,
+            saved_at: saved_at?,
+        };
+        progress.migrate(version);
+        Some(progress).filter(|p| SCENES.contains(&p.scene))
+    }
+
+    /// Brings a save written by an older version up to date.
+    fn migrate(&mut self, _version: u32) {
+        // nothing has changed since the first version that shipped
+    }
+
+    pub fn serialize(&self) -> String {
+        let mut text = format!(
+            "version {}\nscene {}\nframe {}\nseed {}\ntitle {}\nsaved_at {}\n",
+            SAVE_VERSION, self.scene, self.frame, self.seed, self.title, self.saved_at
+        );
+        for (name, value) in self.variables.iter() {
+            text += &format!("var {} {}\n", name, value);
+        }
+        for (name, note) in self.notes.iter() {
+            text += &format!("note {} {}\n", name, escape(note));
+        }
+        text
+    }
+
+    pub fn load(path: &Path) -> Option<Progress> {
+        Progress::parse(&fs::read_to_string(path).ok()?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.serialize())
+    }
+}
+
+/// Keeps a note on one line of a save file.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+/// The per-user directory this book keeps its files in.
+pub fn data_dir() -> PathBuf {
+    let env = |key| std::env::var_os(key).map(PathBuf::from);
+    let base = if cfg!(windows) {
+        env("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env("XDG_DATA_HOME").or_else(|| env("HOME").map(|home| home.join(".local/share")))
+    };
+    base.unwrap_or_else(|| PathBuf::from(".")).join("monster")
+}
+
+pub fn autosave_path() -> PathBuf {
+    data_dir().join("progress.sav")
+}
+
+/// Number of manual save slots, numbered from 1.
+pub const SLOT_COUNT: usize = 4;
+
+pub fn slot_path(slot: usize) -> PathBuf {
+    data_dir().join(format!("slot{}.sav", slot))
+}
+
+pub fn thumbnail_path(slot: usize) -> PathBuf {
+    data_dir().join(format!("slot{}.png", slot))
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD HH:MM` UTC.
+pub fn format_timestamp(secs: u64) -> String {
+    if secs == 0 {
+        return "unknown time".to_string();
+    }
+    // days since the epoch to a civil date, from Howard Hinnant's `civil_from_days`
+    let days = (secs / 86400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        secs % 86400 / 3600,
+        secs % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut variables = BTreeMap::new();
+        variables.insert("greeted_juror".to_string(), 2);
+        let mut notes = BTreeMap::new();
+        notes.insert("student".to_string(), "Steve \\ Harmon".to_string());
+        notes.insert(
+            "journal.verdict".to_string(),
+            "first line\nsecond line".to_string(),
+        );
+        let progress = Progress::new(2, 1560, 42, variables, notes);
+
+        assert_eq!(Progress::parse(&progress.serialize()), Some(progress));
+    }
+
+    #[test]
+    fn rejects_newer_versions_and_unknown_scenes() {
+        let text = Progress::new(2, 0, 7, BTreeMap::new(), BTreeMap::new()).serialize();
+        assert!(Progress::parse(&text).is_some());
+
+        let newer = format!("version {}\n", SAVE_VERSION + 1);
+        let newer = text.replacen(&format!("version {}\n", SAVE_VERSION), &newer, 1);
+        assert_eq!(Progress::parse(&newer), None);
+        assert_eq!(
+            Progress::parse(&text.replace("scene 2\n", "scene 7\n")),
+            None
+        );
+        assert_eq!(Progress::parse(&text.replace("scene 2\n", "")), None);
+    }
+
+    #[test]
+    fn formats_timestamps_as_utc() {
+        assert_eq!(format_timestamp(0), "unknown time");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_timestamp(1_792_413_045), "2026-10-19 12:30");
+    }
+}
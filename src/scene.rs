@@ -0,0 +1,96 @@
+use crate::transition::Transition;
+use raylib::core::drawing::RaylibDrawHandle;
+use raylib::core::texture::{Image, Texture2D};
+use raylib::{RaylibHandle, RaylibThread};
+
+/// The name of a scene, as shown in the save menu.
+pub fn scene_title(scene: u16) -> &'static str {
+    match scene {
+        0 => "Title",
+        1 => "A Note",
+        2 => "The Courtroom",
+        3 => "Cross-Examination",
+        4 => "The Verdict",
+        5 => "Your Name",
+        10 => "Quiz: The Courtroom",
+        11 => "Quiz: Cross-Examination",
+        12 => "Quiz Results",
+        u16::MAX => "The End",
+        _ => "Unknown",
+    }
+}
+
+/// Every scene in the book, in numbering order.
+pub const SCENES: [u16; 10] = [0, 1, 2, 3, 4, 5, 10, 11, 12, u16::MAX];
+
+/// Keeps track of which scene (stage) the book is on and plays transitions between them.
+pub struct SceneManager {
+    current: u16,
+    transition: Option<(Transition, i32)>,
+    /// Set when a scene switch was requested this frame and the old scene still has to be
+    /// captured before the frame ends.
+    capture_pending: bool,
+    captured: Option<Image>,
+    old_scene: Option<Texture2D>,
+}
+
+impl SceneManager {
+    pub fn new(first: u16) -> SceneManager {
+        SceneManager {
+            current: first,
+            transition: None,
+            capture_pending: false,
+            captured: None,
+            old_scene: None,
+        }
+    }
+
+    pub fn current(&self) -> u16 {
+        self.current
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Switches to another scene, playing `transition` over the rest of this frame's scene and
+    /// the start of the next one.
+    pub fn switch_to(&mut self, scene: u16, transition: Transition) {
+        self.current = scene;
+        self.transition = Some((transition, 0));
+        self.capture_pending = true;
+        self.old_scene = None;
+    }
+
+    /// Advances the running transition. Call once per frame, before drawing.
+    pub fn update(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) {
+        if let Some(captured) = self.captured.take() {
+            self.old_scene = rl.load_texture_from_image(thread, &captured).ok();
+        }
+        if let Some((transition, frame)) = self.transition.as_mut() {
+            *frame += 1;
+            if *frame >= transition.duration {
+                self.transition = None;
+                self.old_scene = None;
+            }
+        }
+    }
+
+    /// Draws the running transition. Call at the end of every frame, after the scene.
+    pub fn draw(&mut self, d: &mut RaylibDrawHandle) {
+        if self.capture_pending {
+            self.capture_pending = false;
+            self.captured = Some(capture_screen());
+        }
+        if let Some((transition, frame)) = self.transition {
+            transition.draw(d, self.old_scene.as_ref(), frame);
+        }
+    }
+}
+
+/// Copies what has been drawn so far this frame.
+pub fn capture_screen() -> Image {
+    // raylib-rs only offers screen capture outside of drawing, where the back buffer no longer
+    // holds the frame
+    unsafe { Image::from_raw(raylib::ffi::GetScreenData()) }
+}
@@ -0,0 +1,541 @@
+use crate::audio::Channel;
+use crate::camera::Shot;
+use crate::cast::{Actor, CastMember};
+use crate::effects::Effect;
+use crate::enums::{BubbleKind, Emote, Gesture, SoundEffect};
+use crate::evidence::Evidence;
+use crate::jury::{JurorId, STARING_JUROR};
+use std::collections::BTreeMap;
+
+/// What a beat does while it is active.
+#[derive(Copy, Clone)]
+pub enum Action {
+    /// Narration or dialogue in the text box. May start with a speaker tag (`Steve: ...`), see
+    /// [`crate::cast::split_speaker`], and mark glossary terms in braces (`{jury}`).
+    Say(&'static str),
+    /// A speech or thought bubble anchored above a character.
+    Bubble(Actor, BubbleKind, &'static str),
+    /// A small icon popping up over a character's head.
+    Emote(Actor, Emote),
+    /// A procedural motion of a character's sprite.
+    Gesture(Actor, Gesture),
+    /// Moves the camera. The latest active shot wins.
+    Camera(Shot),
+    /// Shows the scene as a flashback for as long as the beat lasts.
+    Flashback,
+    /// Shakes, flashes or punches in the camera, dying down over the beat.
+    Effect(Effect),
+    /// Starts looping a track on the music or ambience channel when the beat starts,
+    /// crossfading over the length of the beat.
+    PlayTrack(Channel, &'static str),
+    /// Fades out a channel over the length of the beat.
+    StopTrack(Channel),
+    /// Plays a sound effect when the beat starts.
+    Sound(SoundEffect),
+    /// Stops the script at the start of the beat until the reader picks one of the options,
+    /// each a label and the value it sets the story variable to.
+    Choice(&'static str, &'static [(&'static str, i32)]),
+    /// Hands the reader a piece of evidence when the beat starts, announcing it for as long as
+    /// the beat lasts.
+    Award(Evidence),
+    /// Changes how a juror, or with `None` the whole jury, feels about Steve when the beat
+    /// starts.
+    Sway(Option<JurorId>, i32),
+}
+
+/// A test on the story variables. Variables that were never set are 0.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum Condition {
+    Is(&'static str, i32),
+}
+
+impl Condition {
+    pub fn holds(self, variables: &BTreeMap<String, i32>) -> bool {
+        match self {
+            Condition::Is(name, value) => variables.get(name).copied().unwrap_or(0) == value,
+        }
+    }
+}
+
+/// Beats that only play when their condition holds.
+pub struct Branch {
+    pub condition: Condition,
+    pub beats: &'static [Beat],
+}
+
+/// A scene's beats together with its conditional branches.
+pub struct Script {
+    pub beats: &'static [Beat],
+    pub branches: &'static [Branch],
+}
+
+impl Script {
+    /// The beats that play with the story variables as they are, in order of their start.
+    pub fn resolve(&self, variables: &BTreeMap<String, i32>) -> Vec<Beat> {
+        let mut beats: Vec<Beat> = self
+            .branches
+            .iter()
+            .filter(|b| b.condition.holds(variables))
+            .flat_map(|b| b.beats.iter())
+            .chain(self.beats.iter())
+            .copied()
+            .collect();
+        // stable, so beats starting together keep their script order
+        beats.sort_by_key(|b| b.start);
+        beats
+    }
+}
+
+/// A single timed entry in a scene script, measured in frames since the scene started.
+#[derive(Copy, Clone)]
+pub struct Beat {
+    pub start: i32,
+    pub end: i32,
+    pub action: Action,
+}
+
+impl Beat {
+    pub fn is_active(&self, frame: i32) -> bool {
+        frame >= self.start && frame < self.end
+    }
+
+    /// How many characters of the line the typewriter has revealed by this frame.
+    pub fn revealed(&self, frame: i32) -> usize {
+        ((frame - self.start).max(0) / 3) as usize
+    }
+
+    /// The character of `text` the typewriter reveals on exactly this frame, if any.
+    pub fn newly_revealed(&self, text: &str, frame: i32) -> Option<char> {
+        let revealed = self.revealed(frame);
+        if revealed == 0 || revealed == self.revealed(frame - 1) {
+            return None;
+        }
+        text.chars().nth(revealed - 1)
+    }
+}
+
+/// Every beat active on this frame, in script order.
+pub fn active_beats(script: &[Beat], frame: i32) -> impl Iterator<Item = &Beat> {
+    script.iter().filter(move |b| b.is_active(frame))
+}
+
+/// Beats starting exactly on this frame, for one-off actions like sounds.
+pub fn starting_beats(script: &[Beat], frame: i32) -> impl Iterator<Item = &Beat> {
+    script.iter().filter(move |b| b.start == frame)
+}
+
+/// The text box line active on this frame, if any.
+pub fn active_line(script: &[Beat], frame: i32) -> Option<(&Beat, &'static str)> {
+    active_beats(script, frame).find_map(|b| match b.action {
+        Action::Say(line) => Some((b, line)),
+        _ => None,
+    })
+}
+
+/// The choice the script is stopped at on this frame, if the reader hasn't made it yet.
+pub fn pending_choice(
+    script: &[Beat],
+    frame: i32,
+    variables: &BTreeMap<String, i32>,
+) -> Option<(&'static str, &'static [(&'static str, i32)])> {
+    active_beats(script, frame).find_map(|b| match b.action {
+        Action::Choice(name, options) if !variables.contains_key(name) => Some((name, options)),
+        _ => None,
+    })
+}
+
+/// Forgets the choices made and evidence awarded at or after `frame`, so going back before a
+/// choice asks it again.
+pub fn forget_from(script: &[Beat], frame: i32, variables: &mut BTreeMap<String, i32>) {
+    for beat in script.iter().filter(|b| b.start >= frame) {
+        match beat.action {
+            Action::Choice(name, _) => {
+                variables.remove(name);
+            }
+            Action::Award(evidence) => {
+                variables.remove(evidence.variable());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Start frames of the text box lines the reader has been shown, oldest first, so they can go
+/// back to an earlier one.
+#[derive(Default)]
+pub struct LineHistory {
+    starts: Vec<i32>,
+}
+
+impl LineHistory {
+    pub fn new() -> LineHistory {
+        LineHistory { starts: Vec::new() }
+    }
+
+    /// The history of a reader who has read the script up to `frame`.
+    pub fn up_to(script: &[Beat], frame: i32) -> LineHistory {
+        let mut starts: Vec<i32> = script
+            .iter()
+            .filter(|b| matches!(b.action, Action::Say(_)) && b.start <= frame)
+            .map(|b| b.start)
+            .collect();
+        starts.sort_unstable();
+        LineHistory { starts }
+    }
+
+    /// Notes the line active on this frame. Call once per frame.
+    pub fn record(&mut self, script: &[Beat], frame: i32) {
+        if let Some((beat, _)) = active_line(script, frame) {
+            if self.starts.last() != Some(&beat.start) {
+                self.starts.push(beat.start);
+            }
+        }
+    }
+
+    /// The frame to jump to for the text box before the one on screen, or for the last one
+    /// shown if the text box is empty.
+    pub fn rewind(&mut self, script: &[Beat], frame: i32) -> Option<i32> {
+        if let Some((beat, _)) = active_line(script, frame) {
+            if self.starts.len() > 1 && self.starts.last() == Some(&beat.start) {
+                self.starts.pop();
+            } else {
+                return None;
+            }
+        }
+        self.starts.last().copied()
+    }
+}
+
+/// The camera shot active on this frame, if any.
+pub fn active_shot(script: &[Beat], frame: i32) -> Option<Shot> {
+    active_beats(script, frame)
+        .filter_map(|b| match b.action {
+            Action::Camera(shot) => Some(shot),
+            _ => None,
+        })
+        .last()
+}
+
+pub fn in_flashback(script: &[Beat], frame: i32) -> bool {
+    active_beats(script, frame).any(|b| matches!(b.action, Action::Flashback))
+}
+
+/// Strength, from 0 to 1, of the white flash marking the start and end of every flashback.
+pub fn flashback_flash(script: &[Beat], frame: i32) -> f32 {
+    const FLASH_FRAMES: f32 = 20.0;
+
+    script
+        .iter()
+        .filter(|b| matches!(b.action, Action::Flashback))
+        .map(|b| {
+            let distance = (frame - b.start).abs().min((frame - b.end).abs());
+            1.0 - distance as f32 / FLASH_FRAMES
+        })
+        .fold(0.0, f32::max)
+}
+
+/// Combined offset and flip of every gesture the actor is performing on this frame.
+pub fn gesture_motion(script: &[Beat], frame: i32, actor: Actor) -> (f32, f32, bool) {
+    active_beats(script, frame).fold((0.0, 0.0, false), |(x, y, flip), b| match b.action {
+        Action::Gesture(a, gesture) if a == actor => {
+            let (dx, dy, turned) = gesture.motion(frame - b.start);
+            (x + dx, y + dy, flip ^ turned)
+        }
+        _ => (x, y, flip),
+    })
+}
+
+/// Values of the `greeted_juror` story variable.
+pub const GREET_WAVE: i32 = 1;
+pub const GREET_LOOK_AWAY: i32 = 2;
+
+/// Stage 2: Steve in the courtroom.
+pub const COURTROOM: Script = Script {
+    beats: &[
+        Beat {
+            start: 0,
+            end: 120,
+            action: Action::PlayTrack(Channel::Ambience, "assets/audio/courtroom_murmur.wav"),
+        },
+        Beat {
+            start: 20,
+            end: 21,
+            action: Action::Sound(SoundEffect::Footsteps),
+        },
+        Beat {
+            start: 100,
+            end: 101,
+            action: Action::Sound(SoundEffect::Gavel),
+        },
+        Beat {
+            start: 151,
+            end: 1550,
+            action: Action::Say(
+                "Steve looks at the {jury} and sees one {juror} staring at him.\n\
+        He looks back at them and makes eye contact.\n\
+        He notices the juror has a somewhat disgusted facial expression.\n\
+        As he continues looking over, he wonders why.\n\
+        He flashes back to the start of the {trial} and what O'Brien said:\n\
+        \"You're young, you're black, and you're on trial: what else do they need to know?\"",
+            ),
+        },
+        Beat {
+            start: 200,
+            end: 500,
+            action: Action::Camera(Shot::Look(120.0, 96.0, 2.0)),
+        },
+        Beat {
+            start: 330,
+            end: 450,
+            action: Action::Emote(Actor::Juror(STARING_JUROR), Emote::Exclamation),
+        },
+        Beat {
+            start: 330,
+            end: 331,
+            action: Action::Sway(Some(STARING_JUROR), -1),
+        },
+        Beat {
+            start: 800,
+            end: 950,
+            action: Action::Emote(Actor::Cast(CastMember::Steve), Emote::Question),
+        },
+        Beat {
+            start: 990,
+            end: 1140,
+            action: Action::Flashback,
+        },
+        Beat {
+            start: 1000,
+            end: 1130,
+            action: Action::Bubble(
+                Actor::Cast(CastMember::DefenseLawyer),
+                BubbleKind::Speech,
+                "You're young, you're black, and you're on trial. What else do they need to know?",
+            ),
+        },
+        Beat {
+            start: 1000,
+            end: 1030,
+            action: Action::Effect(Effect::Punch(1.15)),
+        },
+        Beat {
+            start: 1000,
+            end: 1120,
+            action: Action::Award(Evidence::OBriensWarning),
+        },
+        Beat {
+            start: 1150,
+            end: 1550,
+            action: Action::Camera(Shot::Focus(Actor::Cast(CastMember::Steve), 1.5)),
+        },
+        Beat {
+            start: 1200,
+            end: 1550,
+            action: Action::Bubble(
+                Actor::Cast(CastMember::Steve),
+                BubbleKind::Thought,
+                "Why me?",
+            ),
+        },
+        Beat {
+            start: 1300,
+            end: 1550,
+            action: Action::Bubble(Actor::Juror(STARING_JUROR), BubbleKind::Thought, "..."),
+        },
+        Beat {
+            start: 1560,
+            end: 1600,
+            action: Action::Choice(
+                "greeted_juror",
+                &[
+                    ("Wave at the juror", GREET_WAVE),
+                    ("Look away", GREET_LOOK_AWAY),
+                ],
+            ),
+        },
+        Beat {
+            start: 2050,
+            end: 2400,
+            action: Action::Camera(Shot::Focus(Actor::Cast(CastMember::Judge), 1.5)),
+        },
+        Beat {
+            start: 3200,
+            end: 3480,
+            action: Action::Flashback,
+        },
+        Beat {
+            start: 3200,
+            end: 3240,
+            action: Action::Effect(Effect::Shake(6.0)),
+        },
+        Beat {
+            start: 2901,
+            end: 3100,
+            action: Action::PlayTrack(Channel::Music, "assets/audio/courtroom_theme.wav"),
+        },
+        Beat {
+            start: 2901,
+            end: 4200,
+            action: Action::Say(
+                "But as he thinks of all this, he worries more and more about \
+        the mainly white {jury}. He flashes back to O'Brien's statement again, and just can't \
+        get it out of his mind. It's hard not to be worried or stressed under this situation.\n\
+        Steve is handling it remarkably well.",
+            ),
+        },
+        Beat {
+            start: 4200,
+            end: 4300,
+            action: Action::StopTrack(Channel::Ambience),
+        },
+        Beat {
+            start: 4200,
+            end: 4300,
+            action: Action::StopTrack(Channel::Music),
+        },
+    ],
+    branches: &[
+        Branch {
+            condition: Condition::Is("greeted_juror", GREET_WAVE),
+            beats: &[
+                Beat {
+                    start: 1601,
+                    end: 2800,
+                    action: Action::Say(
+                        "To try and break the ice, he waves.\n\
+        The juror snarls and looks away.\n\
+        With a heavy heart, he looks up at the American flag over the judge and places \
+        his trust in the 12 members of the jury who are the ones to decide his fate.\n\
+        He knows he is innocent and hopes the justice system sees him the same way.",
+                    ),
+                },
+                Beat {
+                    start: 1690,
+                    end: 1810,
+                    action: Action::Gesture(Actor::Cast(CastMember::Steve), Gesture::Wave),
+                },
+                Beat {
+                    start: 1690,
+                    end: 1691,
+                    action: Action::Sway(None, 1),
+                },
+                Beat {
+                    start: 1740,
+                    end: 1900,
+                    action: Action::Emote(Actor::Juror(STARING_JUROR), Emote::Anger),
+                },
+                Beat {
+                    start: 1740,
+                    end: 1741,
+                    action: Action::Sway(Some(STARING_JUROR), -3),
+                },
+                Beat {
+                    start: 1800,
+                    end: 2800,
+                    action: Action::Gesture(Actor::Juror(STARING_JUROR), Gesture::TurnAway),
+                },
+            ],
+        },
+        Branch {
+            condition: Condition::Is("greeted_juror", GREET_LOOK_AWAY),
+            beats: &[
+                Beat {
+                    start: 1601,
+                    end: 2800,
+                    action: Action::Say(
+                        "Steve drops his eyes to the table instead.\n\
+        He can still feel the juror's stare on the side of his face.\n\
+        Don't give them anything, he tells himself. Whatever you do, they already see a monster.\n\
+        When he finally looks up, it is at the American flag over the judge. He places \
+        his trust in the 12 members of the jury who are the ones to decide his fate.\n\
+        He knows he is innocent and hopes the justice system sees him the same way.",
+                    ),
+                },
+                Beat {
+                    start: 1650,
+                    end: 2000,
+                    action: Action::Camera(Shot::Focus(Actor::Cast(CastMember::Steve), 1.5)),
+                },
+                Beat {
+                    start: 1700,
+                    end: 1701,
+                    action: Action::Sway(None, -1),
+                },
+                Beat {
+                    start: 1700,
+                    end: 1950,
+                    action: Action::Bubble(
+                        Actor::Cast(CastMember::Steve),
+                        BubbleKind::Thought,
+                        "Don't look at them.",
+                    ),
+                },
+            ],
+        },
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_starts(script: &[Beat]) -> Vec<i32> {
+        script
+            .iter()
+            .filter(|b| matches!(b.action, Action::Say(_)))
+            .map(|b| b.start)
+            .collect()
+    }
+
+    #[test]
+    fn resolves_only_the_chosen_branch() {
+        let mut variables = BTreeMap::new();
+        assert_eq!(line_starts(&COURTROOM.resolve(&variables)), vec![151, 2901]);
+        variables.insert("greeted_juror".to_string(), GREET_LOOK_AWAY);
+        let script = COURTROOM.resolve(&variables);
+        assert_eq!(line_starts(&script), vec![151, 1601, 2901]);
+        assert!(active_line(&script, 1601)
+            .unwrap()
+            .1
+            .starts_with("Steve drops his eyes"));
+    }
+
+    #[test]
+    fn rewinding_across_a_branch_forgets_the_choice() {
+        let obrien = Evidence::OBriensWarning.variable();
+        let mut variables = BTreeMap::new();
+        variables.insert("greeted_juror".to_string(), GREET_WAVE);
+        variables.insert(obrien.to_string(), 1);
+        let mut script = COURTROOM.resolve(&variables);
+        let mut history = LineHistory::up_to(&script, 3000);
+
+        // back to the branch's line, which still depends on the choice
+        let frame = history.rewind(&script, 3000).unwrap();
+        assert_eq!(frame, 1601);
+        forget_from(&script, frame, &mut variables);
+        assert_eq!(variables.get("greeted_juror"), Some(&GREET_WAVE));
+        assert!(variables.contains_key(obrien));
+
+        // back before the choice, and before the evidence was handed over
+        let frame = history.rewind(&script, frame).unwrap();
+        assert_eq!(frame, 151);
+        forget_from(&script, frame, &mut variables);
+        assert!(variables.is_empty());
+        script = COURTROOM.resolve(&variables);
+        assert_eq!(line_starts(&script), vec![151, 2901]);
+        assert!(pending_choice(&script, 1560, &variables).is_some());
+
+        // nothing to go back to from the first line
+        assert_eq!(history.rewind(&script, frame), None);
+    }
+
+    #[test]
+    fn rewinding_to_an_empty_text_box_goes_to_the_last_line() {
+        let script = COURTROOM.resolve(&BTreeMap::new());
+        let mut history = LineHistory::new();
+        for frame in 0..1560 {
+            history.record(&script, frame);
+        }
+        assert_eq!(history.rewind(&script, 1555), Some(151));
+    }
+}
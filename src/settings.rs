@@ -0,0 +1,38 @@
+/// How the jury's feelings about Steve are shown while reading.
+#[derive(Default, PartialEq, Debug, Copy, Clone)]
+pub enum SentimentDisplay {
+    #[default]
+    Hidden,
+    /// A meter of the whole jury's mood in the corner of the screen.
+    Meter,
+    /// Hearts and anger marks over the jurors who feel strongly.
+    Expressions,
+}
+
+/// Reader preferences.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Settings {
+    /// Turns off camera shake and zoom punches, and tones down flashes.
+    pub reduced_motion: bool,
+    pub sentiment: SentimentDisplay,
+    /// Stops for a short quiz after each chapter, with the results at the end.
+    pub quiz: bool,
+}
+
+impl Settings {
+    /// Reads settings from command line flags, e.g. `monster --reduced-motion` or
+    /// `monster --sentiment=meter`.
+    pub fn from_args(args: impl Iterator<Item = String>) -> Settings {
+        let mut settings = Settings::default();
+        for arg in args {
+            match arg.as_str() {
+                "--reduced-motion" => settings.reduced_motion = true,
+                "--quiz" => settings.quiz = true,
+                "--sentiment=meter" => settings.sentiment = SentimentDisplay::Meter,
+                "--sentiment=expressions" => settings.sentiment = SentimentDisplay::Expressions,
+                _ => {}
+            }
+        }
+        settings
+    }
+}
@@ -0,0 +1,189 @@
+use crate::drawing::draw_text;
+use crate::save::{format_timestamp, slot_path, thumbnail_path, Progress, SLOT_COUNT};
+use crate::scene::capture_screen;
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::logging::trace_log;
+use raylib::core::math::Rectangle;
+use raylib::core::texture::{Image, Texture2D};
+use raylib::ffi::{KeyboardKey, TraceLogType};
+use raylib::{RaylibHandle, RaylibThread};
+
+/// Size of the screenshot kept with each save.
+const THUMBNAIL_SIZE: i32 = 104;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SlotMode {
+    Save,
+    Load,
+}
+
+/// The save/load menu over the numbered save slots.
+pub struct SlotMenu {
+    mode: Option<SlotMode>,
+    selected: usize,
+    /// What each slot holds, read from disk.
+    slots: Vec<(Option<Progress>, Option<Texture2D>)>,
+    /// Set when the slots changed on disk and have to be read again before the next draw.
+    stale: bool,
+    /// Set when the menu was opened for saving and the frame under it still has to be captured.
+    capture_pending: bool,
+    screenshot: Option<Image>,
+}
+
+impl SlotMenu {
+    pub fn new() -> SlotMenu {
+        SlotMenu {
+            mode: None,
+            selected: 0,
+            slots: Vec::new(),
+            stale: true,
+            capture_pending: false,
+            screenshot: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.mode.is_some()
+    }
+
+    /// Opens the menu in `mode`, or closes it if it is already open in that mode.
+    pub fn toggle(&mut self, mode: SlotMode) {
+        if self.mode == Some(mode) {
+            self.close();
+            return;
+        }
+        self.mode = Some(mode);
+        self.capture_pending = mode == SlotMode::Save;
+    }
+
+    pub fn close(&mut self) {
+        self.mode = None;
+        self.capture_pending = false;
+        self.screenshot = None;
+    }
+
+    /// Reads the slots again if they changed. Call once per frame, before drawing.
+    pub fn update(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) {
+        if !self.stale {
+            return;
+        }
+        self.stale = false;
+        self.slots = (1..=SLOT_COUNT)
+            .map(|slot| {
+                let progress = Progress::load(&slot_path(slot));
+                let thumbnail = thumbnail_path(slot);
+                let thumbnail = if progress.is_some() && thumbnail.exists() {
+                    rl.load_texture(thread, &thumbnail.to_string_lossy()).ok()
+                } else {
+                    None
+                };
+                (progress, thumbnail)
+            })
+            .collect();
+    }
+
+    /// Handles input and draws the menu. Call at the end of every frame, after the scene.
+    ///
+    /// `current` is what saving writes to the selected slot. Returns the progress to resume from
+    /// when a slot was loaded.
+    pub fn draw(&mut self, d: &mut RaylibDrawHandle, current: &Progress) -> Option<Progress> {
+        let mode = self.mode?;
+        if self.capture_pending {
+            self.capture_pending = false;
+            let mut screenshot = capture_screen();
+            screenshot.resize(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+            self.screenshot = Some(screenshot);
+        }
+
+        if d.is_key_pressed(KeyboardKey::KEY_UP) {
+            self.selected = (self.selected + SLOT_COUNT - 1) % SLOT_COUNT;
+        } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+            self.selected = (self.selected + 1) % SLOT_COUNT;
+        }
+
+        let mut resume = None;
+        if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+            let slot = self.selected + 1;
+            match mode {
+                SlotMode::Save => {
+                    if let Err(e) = current.save(&slot_path(slot)) {
+                        trace_log(
+                            TraceLogType::LOG_WARNING,
+                            format!("failed to save to slot {}: {}", slot, e).as_str(),
+                        );
+                    } else if let Some(screenshot) = self.screenshot.as_ref() {
+                        screenshot.export_image(&thumbnail_path(slot).to_string_lossy());
+                    }
+                    self.stale = true;
+                }
+                SlotMode::Load => {
+                    resume = self
+                        .slots
+                        .get(self.selected)
+                        .and_then(|(progress, _)| progress.clone());
+                    if resume.is_some() {
+                        self.close();
+                    }
+                }
+            }
+        }
+
+        d.draw_rectangle(0, 0, 624, 624, Color::BLACK.fade(0.6));
+        d.draw_rectangle_rounded(
+            Rectangle::new(62.0, 30.0, 500.0, 564.0),
+            0.05,
+            10,
+            Color::WHITE,
+        );
+        let heading = match mode {
+            SlotMode::Save => "Save",
+            SlotMode::Load => "Load",
+        };
+        draw_text(d, heading, 30, 312, 60, None);
+
+        for slot in 0..SLOT_COUNT {
+            let y = 90 + slot as i32 * 118;
+            if slot == self.selected {
+                d.draw_rectangle_lines_ex(
+                    Rectangle::new(74.0, y as f32 - 4.0, 476.0, 114.0),
+                    2,
+                    Color::BLACK,
+                );
+            }
+
+            d.draw_rectangle_lines(82, y + 1, THUMBNAIL_SIZE, THUMBNAIL_SIZE, Color::GRAY);
+            d.draw_text(&format!("Slot {}", slot + 1), 200, y + 8, 20, Color::BLACK);
+            match self.slots.get(slot) {
+                Some((Some(progress), thumbnail)) => {
+                    if let Some(thumbnail) = thumbnail {
+                        d.draw_texture(thumbnail, 82, y + 1, Color::WHITE);
+                    }
+                    d.draw_text(&progress.title, 200, y + 40, 20, Color::BLACK);
+                    d.draw_text(
+                        &format_timestamp(progress.saved_at),
+                        200,
+                        y + 70,
+                        16,
+                        Color::DARKGRAY,
+                    );
+                }
+                _ => d.draw_text("Empty", 200, y + 40, 20, Color::GRAY),
+            }
+        }
+
+        let hint = match mode {
+            SlotMode::Save => "ENTER to save, F5 to close",
+            SlotMode::Load => "ENTER to load, F9 to close",
+        };
+        draw_text(d, hint, 20, 312, 570, None);
+
+        resume
+    }
+}
+
+impl Default for SlotMenu {
+    fn default() -> SlotMenu {
+        SlotMenu::new()
+    }
+}
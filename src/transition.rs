@@ -0,0 +1,108 @@
+use crate::tween::{EaseFn, Tween};
+use raylib::core::color::Color;
+use raylib::core::drawing::RaylibDraw;
+use raylib::core::math::{Rectangle, Vector2};
+use raylib::core::texture::Texture2D;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum TransitionKind {
+    /// Fades the old scene out to a solid color, then fades the new one in from it.
+    Fade(Color),
+    /// Blends the old scene into the new one.
+    Crossfade,
+    /// Uncovers the new scene from left to right.
+    Wipe,
+    /// Closes a black circle onto the center of the old scene, then opens it onto the new one.
+    Iris,
+}
+
+/// How to get from one scene to the next.
+#[derive(Copy, Clone)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    /// Length of the whole transition in frames.
+    pub duration: i32,
+    /// An easing function from [`raylib::ease`].
+    pub ease: EaseFn,
+}
+
+impl Transition {
+    pub fn new(kind: TransitionKind, duration: i32, ease: EaseFn) -> Transition {
+        Transition {
+            kind,
+            duration,
+            ease,
+        }
+    }
+
+    /// Eased progress from 0 to 1 over `duration` frames.
+    fn eased(&self, frame: i32, duration: i32) -> f32 {
+        Tween::new(0.0, 1.0, duration.max(1) as f32, self.ease).advance(frame as f32)
+    }
+
+    /// Draws the transition `frame` frames in over the new scene, given a snapshot of the last
+    /// frame of the old one.
+    pub fn draw(&self, d: &mut impl RaylibDraw, old_scene: Option<&Texture2D>, frame: i32) {
+        let half = self.duration / 2;
+        let screen = Rectangle::new(0.0, 0.0, 624.0, 624.0);
+
+        match self.kind {
+            TransitionKind::Fade(color) => {
+                let alpha = if frame < half {
+                    if let Some(old_scene) = old_scene {
+                        d.draw_texture(old_scene, 0, 0, Color::WHITE);
+                    }
+                    self.eased(frame, half)
+                } else {
+                    1.0 - self.eased(frame - half, self.duration - half)
+                };
+                d.draw_rectangle_rec(
+                    screen,
+                    Color::new(color.r, color.g, color.b, to_alpha(alpha)),
+                );
+            }
+            TransitionKind::Crossfade => {
+                if let Some(old_scene) = old_scene {
+                    let alpha = 1.0 - self.eased(frame, self.duration);
+                    d.draw_texture(old_scene, 0, 0, Color::new(255, 255, 255, to_alpha(alpha)));
+                }
+            }
+            TransitionKind::Wipe => {
+                if let Some(old_scene) = old_scene {
+                    let edge = self.eased(frame, self.duration) * screen.width;
+                    d.draw_texture_rec(
+                        old_scene,
+                        Rectangle::new(edge, 0.0, screen.width - edge, screen.height),
+                        Vector2::new(edge, 0.0),
+                        Color::WHITE,
+                    );
+                }
+            }
+            TransitionKind::Iris => {
+                // far enough from the center to cover the corners
+                let max_radius = 450.0;
+                let radius = if frame < half {
+                    if let Some(old_scene) = old_scene {
+                        d.draw_texture(old_scene, 0, 0, Color::WHITE);
+                    }
+                    (1.0 - self.eased(frame, half)) * max_radius
+                } else {
+                    self.eased(frame - half, self.duration - half) * max_radius
+                };
+                d.draw_ring(
+                    Vector2::new(312.0, 312.0),
+                    radius,
+                    max_radius,
+                    0,
+                    360,
+                    64,
+                    Color::BLACK,
+                );
+            }
+        }
+    }
+}
+
+fn to_alpha(opacity: f32) -> u8 {
+    (opacity.clamp(0.0, 1.0) * 255.0) as u8
+}
@@ -0,0 +1,200 @@
+//! Interpolation of values over time.
+//!
+//! Easing curves come from [`raylib::ease`] (`linear_none`, `quad_in_out`, `cubic_out`,
+//! `elastic_out`, ...), so anything that takes an [`EaseFn`] can use them.
+
+use raylib::core::color::Color;
+use raylib::core::math::Vector2;
+pub use raylib::ease::{self, EaseFn};
+
+/// A value that can be blended between two endpoints.
+pub trait Lerp: Copy {
+    /// Blends from `self` at `t = 0` to `to` at `t = 1`. `t` may leave that range when an
+    /// easing curve overshoots.
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: f32, t: f32) -> f32 {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for Vector2 {
+    fn lerp(self, to: Vector2, t: f32) -> Vector2 {
+        Vector2::new(self.x.lerp(to.x, t), self.y.lerp(to.y, t))
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(self, to: Color, t: f32) -> Color {
+        let channel =
+            |from: u8, to: u8| (from as f32).lerp(to as f32, t).round().clamp(0.0, 255.0) as u8;
+        Color::new(
+            channel(self.r, to.r),
+            channel(self.g, to.g),
+            channel(self.b, to.b),
+            channel(self.a, to.a),
+        )
+    }
+}
+
+/// Animates a value from one endpoint to another.
+///
+/// Time is whatever unit the caller advances it by; everything in this crate counts frames.
+pub struct Tween<T: Lerp> {
+    from: T,
+    to: T,
+    duration: f32,
+    elapsed: f32,
+    ease: EaseFn,
+    on_complete: Option<Box<dyn FnOnce()>>,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub fn new(from: T, to: T, duration: f32, ease: EaseFn) -> Tween<T> {
+        Tween {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+            ease,
+            on_complete: None,
+        }
+    }
+
+    /// Runs `callback` once, on the step that finishes the tween.
+    pub fn on_complete(mut self, callback: impl FnOnce() + 'static) -> Tween<T> {
+        self.on_complete = Some(Box::new(callback));
+        self
+    }
+
+    /// Moves the tween forward by `step` and returns the new value.
+    pub fn advance(&mut self, step: f32) -> T {
+        self.elapsed = (self.elapsed + step).min(self.duration).max(0.0);
+        if self.is_complete() {
+            if let Some(callback) = self.on_complete.take() {
+                callback();
+            }
+        }
+        self.value()
+    }
+
+    pub fn value(&self) -> T {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let t = (self.ease)(self.elapsed, 0.0, 1.0, self.duration);
+        self.from.lerp(self.to, t)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Starts over from the beginning. A callback that already ran does not run again.
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// Starts a new tween from the current value, keeping the duration and easing.
+    pub fn retarget(&mut self, to: T) {
+        self.from = self.value();
+        self.to = to;
+        self.elapsed = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn linear_f32_reaches_halfway_and_end() {
+        let mut tween = Tween::new(0.0, 10.0, 4.0, ease::linear_none);
+        assert_eq!(tween.advance(2.0), 5.0);
+        assert!(!tween.is_complete());
+        assert_eq!(tween.advance(2.0), 10.0);
+        assert!(tween.is_complete());
+    }
+
+    #[test]
+    fn does_not_overshoot_duration() {
+        let mut tween = Tween::new(1.0, 3.0, 10.0, ease::quad_in);
+        assert_eq!(tween.advance(25.0), 3.0);
+        assert_eq!(tween.advance(1.0), 3.0);
+    }
+
+    #[test]
+    fn easing_shapes_the_curve() {
+        let mut quad_in = Tween::new(0.0, 1.0, 10.0, ease::quad_in);
+        let mut quad_out = Tween::new(0.0, 1.0, 10.0, ease::quad_out);
+        let mut cubic_in = Tween::new(0.0, 1.0, 10.0, ease::cubic_in);
+        assert!(quad_in.advance(5.0) < 0.5);
+        assert!(quad_out.advance(5.0) > 0.5);
+        assert!(cubic_in.advance(5.0) < 0.25);
+    }
+
+    #[test]
+    fn elastic_overshoots_past_the_target() {
+        let mut tween = Tween::new(0.0, 1.0, 100.0, ease::elastic_out);
+        let overshoot = (0..100)
+            .map(|_| tween.advance(1.0))
+            .fold(f32::MIN, f32::max);
+        assert!(overshoot > 1.0);
+        assert!((tween.value() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn vector2_interpolates_both_axes() {
+        let mut tween = Tween::new(
+            Vector2::new(0.0, 100.0),
+            Vector2::new(50.0, 0.0),
+            2.0,
+            ease::linear_none,
+        );
+        let halfway = tween.advance(1.0);
+        assert_eq!(halfway.x, 25.0);
+        assert_eq!(halfway.y, 50.0);
+    }
+
+    #[test]
+    fn color_interpolates_and_clamps() {
+        let black = Color::new(0, 0, 0, 0);
+        let white = Color::new(255, 255, 255, 255);
+        assert_eq!(black.lerp(white, 0.5), Color::new(128, 128, 128, 128));
+        assert_eq!(black.lerp(white, 1.5), white);
+        assert_eq!(white.lerp(black, 1.5), black);
+    }
+
+    #[test]
+    fn completion_callback_runs_once() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let mut tween = Tween::new(0.0, 1.0, 3.0, ease::linear_none)
+            .on_complete(move || counter.set(counter.get() + 1));
+        tween.advance(2.0);
+        assert_eq!(calls.get(), 0);
+        tween.advance(1.0);
+        tween.advance(1.0);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn zero_duration_is_already_complete() {
+        let tween = Tween::new(0.0, 7.0, 0.0, ease::linear_none);
+        assert!(tween.is_complete());
+        assert_eq!(tween.value(), 7.0);
+    }
+
+    #[test]
+    fn retarget_starts_from_current_value() {
+        let mut tween = Tween::new(0.0, 10.0, 10.0, ease::linear_none);
+        tween.advance(5.0);
+        tween.retarget(0.0);
+        assert_eq!(tween.value(), 5.0);
+        assert_eq!(tween.advance(10.0), 0.0);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::drawing::{draw_choice_menu, draw_portrait, draw_text, wrap_text};
+use crate::enums::PersonType;
+use crate::evidence::{collected, draw_evidence_list};
+use crate::jury::Jury;
+use raylib::core::color::Color;
+use raylib::core::drawing::{RaylibDraw, RaylibDrawHandle};
+use raylib::core::math::Rectangle;
+use raylib::core::texture::Texture2D;
+use raylib::ffi::KeyboardKey;
+use std::collections::{BTreeMap, HashMap};
+
+/// Story variable holding the reader's verdict, once they cast it.
+pub const VERDICT: &str = "verdict";
+pub const GUILTY: i32 = 1;
+pub const NOT_GUILTY: i32 = 2;
+/// What the jury decides at the end of the novel.
+pub const BOOK_VERDICT: i32 = NOT_GUILTY;
+
+/// The end of the book: the reader sits with the jury, reviews the evidence and votes.
+pub struct Deliberation {
+    /// Reviewing the evidence, or voting once done with that.
+    voting: bool,
+    selected: usize,
+}
+
+impl Deliberation {
+    pub fn new() -> Deliberation {
+        Deliberation {
+            voting: false,
+            selected: 0,
+        }
+    }
+
+    /// Handles input and draws the scene. `mood` is the whole jury's sentiment towards Steve,
+    /// which colors the ending. Returns true once the reader has read the outcome and wants to
+    /// move on.
+    pub fn draw(
+        &mut self,
+        d: &mut RaylibDrawHandle,
+        person_texture: &HashMap<PersonType, Texture2D>,
+        jury: &Jury,
+        variables: &mut BTreeMap<String, i32>,
+        paused: bool,
+        mood: i32,
+    ) -> bool {
+        draw_text(d, "The Jury Box", 30, 312, 30, None);
+        for (i, juror) in jury.iter().enumerate() {
+            let frame = Rectangle::new(48.0 + i as f32 * 44.0, 56.0, 40.0, 40.0);
+            draw_portrait(d, person_texture, juror.person_type, frame);
+        }
+
+        if let Some(verdict) = variables.get(VERDICT).copied() {
+            draw_outcome(d, verdict, mood);
+            return !paused && d.is_key_pressed(KeyboardKey::KEY_ENTER);
+        }
+
+        if self.voting {
+            let options = [("Not guilty", NOT_GUILTY), ("Guilty", GUILTY)];
+            if !paused {
+                if d.is_key_pressed(KeyboardKey::KEY_UP) || d.is_key_pressed(KeyboardKey::KEY_DOWN)
+                {
+                    self.selected = 1 - self.selected;
+                } else if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                    variables.insert(VERDICT.to_string(), options[self.selected].1);
+                } else if d.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+                    self.voting = false;
+                    self.selected = 0;
+                }
+            }
+            draw_text(
+                d,
+                "Is Steve Harmon guilty of felony murder?",
+                20,
+                312,
+                130,
+                None,
+            );
+            draw_choice_menu(d, &options, self.selected);
+            draw_text(
+                d,
+                "BACKSPACE to review the evidence again",
+                20,
+                312,
+                600,
+                None,
+            );
+            return false;
+        }
+
+        let evidence = collected(variables);
+        if !paused {
+            if !evidence.is_empty() {
+                if d.is_key_pressed(KeyboardKey::KEY_UP) {
+                    self.selected = (self.selected + evidence.len() - 1) % evidence.len();
+                } else if d.is_key_pressed(KeyboardKey::KEY_DOWN) {
+                    self.selected = (self.selected + 1) % evidence.len();
+                }
+            }
+            if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                self.voting = true;
+                self.selected = 0;
+            }
+        }
+        draw_text(d, "Review the evidence", 20, 312, 130, None);
+        draw_evidence_list(d, &evidence, self.selected, 152);
+        draw_text(d, "ENTER when you are ready to vote", 20, 312, 600, None);
+        false
+    }
+}
+
+fn draw_outcome(d: &mut RaylibDrawHandle, verdict: i32, mood: i32) {
+    let (yours, agreement) = if verdict == BOOK_VERDICT {
+        (
+            "You found Steve not guilty.",
+            "The jury in the book agreed with you.",
+        )
+    } else {
+        (
+            "You found Steve guilty.",
+            "The jury in the book did not agree with you.",
+        )
+    };
+    draw_text(d, yours, 24, 312, 160, None);
+    draw_text(d, agreement, 20, 312, 200, None);
+    let jury = match mood {
+        m if m > 0 => "By the end, most of the jurors seemed to be on Steve's side.",
+        m if m < 0 => "Most of the jurors never stopped seeing a monster when they looked at him.",
+        _ => "The jurors' faces gave nothing away.",
+    };
+    draw_text(d, jury, 16, 312, 230, None);
+
+    let ending = "In the novel, Steve is found not guilty, while King is convicted. When Steve \
+    turns to hug O'Brien after the verdict, she turns away, and he is left wondering what she \
+    saw in him. Whether or not he is a monster is a question he keeps asking himself.";
+    for (i, line) in wrap_text(ending, 20, 500).iter().enumerate() {
+        d.draw_text(line, 62, 260 + i as i32 * 24, 20, Color::BLACK);
+    }
+    draw_text(d, "Press ENTER to continue...", 24, 312, 600, None);
+}
+
+impl Default for Deliberation {
+    fn default() -> Deliberation {
+        Deliberation::new()
+    }
+}